@@ -0,0 +1,130 @@
+//! Compares the buffered serde path (`parse_feed`) with the streaming
+//! `FeedReader` on a synthetic 2000-entry response.
+//!
+//! Run with `cargo bench --bench parse_feed`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use arxiv_api_rs::{parse_feed, FeedReader};
+
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const N_ENTRIES: usize = 2000;
+const N_ITERATIONS: u32 = 10;
+
+fn synthetic_feed() -> Vec<u8> {
+    let fixture = include_str!("../testdata/feed.xml");
+    let entries_start = fixture.find("<entry>").unwrap();
+    let entries_end = fixture.rfind("</entry>").unwrap() + "</entry>".len();
+    let entries = &fixture[entries_start..entries_end];
+    let n_fixture_entries = entries.matches("<entry>").count();
+
+    let mut feed = fixture[..entries_start].to_string();
+    for i in 0..N_ENTRIES.div_ceil(n_fixture_entries) {
+        feed.push_str(&entries.replace("v1</id>", &format!("v{}</id>", i + 1)));
+    }
+    feed.push_str(&fixture[entries_end..]);
+    feed.into_bytes()
+}
+
+struct Measurement {
+    elapsed: Duration,
+    allocated: usize,
+    peak: usize,
+}
+
+fn measure(mut f: impl FnMut() -> usize) -> Measurement {
+    let mut elapsed = Duration::ZERO;
+    let mut allocated = 0;
+    let mut peak = 0;
+
+    for _ in 0..N_ITERATIONS {
+        let baseline = CURRENT.load(Ordering::Relaxed);
+        ALLOCATED.store(0, Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+
+        let start = Instant::now();
+        let n_results = f();
+        elapsed += start.elapsed();
+
+        assert!(n_results >= N_ENTRIES);
+        allocated = allocated.max(ALLOCATED.load(Ordering::Relaxed));
+        peak = peak.max(PEAK.load(Ordering::Relaxed) - baseline);
+    }
+
+    Measurement {
+        elapsed: elapsed / N_ITERATIONS,
+        allocated,
+        peak,
+    }
+}
+
+fn report(name: &str, measurement: &Measurement) {
+    println!(
+        "{:<28} {:>10.2?} {:>14} {:>14}",
+        name,
+        measurement.elapsed,
+        format!("{} KiB", measurement.allocated / 1024),
+        format!("{} KiB", measurement.peak / 1024),
+    );
+}
+
+fn main() {
+    let body = synthetic_feed();
+    println!("feed: {} entries, {} KiB\n", N_ENTRIES, body.len() / 1024);
+    println!(
+        "{:<28} {:>10} {:>14} {:>14}",
+        "path", "time", "allocated", "peak"
+    );
+
+    // Mirrors `ArxivClient::search`: the body is first copied into a String.
+    report(
+        "serde (buffered)",
+        &measure(|| {
+            let text = String::from_utf8(body.clone()).unwrap();
+            parse_feed(&text).unwrap().len()
+        }),
+    );
+
+    report(
+        "streaming (collected)",
+        &measure(|| {
+            FeedReader::new(body.as_slice())
+                .collect::<anyhow::Result<Vec<_>>>()
+                .unwrap()
+                .len()
+        }),
+    );
+
+    report(
+        "streaming (incremental)",
+        &measure(|| {
+            FeedReader::new(body.as_slice())
+                .filter(|result| result.is_ok())
+                .count()
+        }),
+    );
+}
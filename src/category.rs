@@ -0,0 +1,476 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum CategoryGroup {
+    ComputerScience,
+    Economics,
+    ElectricalEngineeringAndSystemsScience,
+    Mathematics,
+    Physics,
+    QuantitativeBiology,
+    QuantitativeFinance,
+    Statistics,
+}
+
+impl CategoryGroup {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CategoryGroup::ComputerScience => "Computer Science",
+            CategoryGroup::Economics => "Economics",
+            CategoryGroup::ElectricalEngineeringAndSystemsScience => {
+                "Electrical Engineering and Systems Science"
+            }
+            CategoryGroup::Mathematics => "Mathematics",
+            CategoryGroup::Physics => "Physics",
+            CategoryGroup::QuantitativeBiology => "Quantitative Biology",
+            CategoryGroup::QuantitativeFinance => "Quantitative Finance",
+            CategoryGroup::Statistics => "Statistics",
+        }
+    }
+}
+
+impl Display for CategoryGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// An entry of the arXiv category taxonomy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CategoryInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub group: CategoryGroup,
+}
+
+impl CategoryInfo {
+    pub fn archive(&self) -> &'static str {
+        archive_of(self.id)
+    }
+}
+
+const fn info(id: &'static str, name: &'static str, group: CategoryGroup) -> CategoryInfo {
+    CategoryInfo { id, name, group }
+}
+
+use CategoryGroup::*;
+
+#[rustfmt::skip]
+const TAXONOMY: &[CategoryInfo] = &[
+    info("cs.AI", "Artificial Intelligence", ComputerScience),
+    info("cs.AR", "Hardware Architecture", ComputerScience),
+    info("cs.CC", "Computational Complexity", ComputerScience),
+    info("cs.CE", "Computational Engineering, Finance, and Science", ComputerScience),
+    info("cs.CG", "Computational Geometry", ComputerScience),
+    info("cs.CL", "Computation and Language", ComputerScience),
+    info("cs.CR", "Cryptography and Security", ComputerScience),
+    info("cs.CV", "Computer Vision and Pattern Recognition", ComputerScience),
+    info("cs.CY", "Computers and Society", ComputerScience),
+    info("cs.DB", "Databases", ComputerScience),
+    info("cs.DC", "Distributed, Parallel, and Cluster Computing", ComputerScience),
+    info("cs.DL", "Digital Libraries", ComputerScience),
+    info("cs.DM", "Discrete Mathematics", ComputerScience),
+    info("cs.DS", "Data Structures and Algorithms", ComputerScience),
+    info("cs.ET", "Emerging Technologies", ComputerScience),
+    info("cs.FL", "Formal Languages and Automata Theory", ComputerScience),
+    info("cs.GL", "General Literature", ComputerScience),
+    info("cs.GR", "Graphics", ComputerScience),
+    info("cs.GT", "Computer Science and Game Theory", ComputerScience),
+    info("cs.HC", "Human-Computer Interaction", ComputerScience),
+    info("cs.IR", "Information Retrieval", ComputerScience),
+    info("cs.IT", "Information Theory", ComputerScience),
+    info("cs.LG", "Machine Learning", ComputerScience),
+    info("cs.LO", "Logic in Computer Science", ComputerScience),
+    info("cs.MA", "Multiagent Systems", ComputerScience),
+    info("cs.MM", "Multimedia", ComputerScience),
+    info("cs.MS", "Mathematical Software", ComputerScience),
+    info("cs.NE", "Neural and Evolutionary Computing", ComputerScience),
+    info("cs.NI", "Networking and Internet Architecture", ComputerScience),
+    info("cs.OH", "Other Computer Science", ComputerScience),
+    info("cs.OS", "Operating Systems", ComputerScience),
+    info("cs.PF", "Performance", ComputerScience),
+    info("cs.PL", "Programming Languages", ComputerScience),
+    info("cs.RO", "Robotics", ComputerScience),
+    info("cs.SC", "Symbolic Computation", ComputerScience),
+    info("cs.SD", "Sound", ComputerScience),
+    info("cs.SE", "Software Engineering", ComputerScience),
+    info("cs.SI", "Social and Information Networks", ComputerScience),
+    info("econ.EM", "Econometrics", Economics),
+    info("econ.GN", "General Economics", Economics),
+    info("econ.TH", "Theoretical Economics", Economics),
+    info("eess.AS", "Audio and Speech Processing", ElectricalEngineeringAndSystemsScience),
+    info("eess.IV", "Image and Video Processing", ElectricalEngineeringAndSystemsScience),
+    info("eess.SP", "Signal Processing", ElectricalEngineeringAndSystemsScience),
+    info("eess.SY", "Systems and Control", ElectricalEngineeringAndSystemsScience),
+    info("math.AC", "Commutative Algebra", Mathematics),
+    info("math.AG", "Algebraic Geometry", Mathematics),
+    info("math.AP", "Analysis of PDEs", Mathematics),
+    info("math.AT", "Algebraic Topology", Mathematics),
+    info("math.CA", "Classical Analysis and ODEs", Mathematics),
+    info("math.CO", "Combinatorics", Mathematics),
+    info("math.CT", "Category Theory", Mathematics),
+    info("math.CV", "Complex Variables", Mathematics),
+    info("math.DG", "Differential Geometry", Mathematics),
+    info("math.DS", "Dynamical Systems", Mathematics),
+    info("math.FA", "Functional Analysis", Mathematics),
+    info("math.GM", "General Mathematics", Mathematics),
+    info("math.GN", "General Topology", Mathematics),
+    info("math.GR", "Group Theory", Mathematics),
+    info("math.GT", "Geometric Topology", Mathematics),
+    info("math.HO", "History and Overview", Mathematics),
+    info("math.KT", "K-Theory and Homology", Mathematics),
+    info("math.LO", "Logic", Mathematics),
+    info("math.MG", "Metric Geometry", Mathematics),
+    info("math.NA", "Numerical Analysis", Mathematics),
+    info("math.NT", "Number Theory", Mathematics),
+    info("math.OA", "Operator Algebras", Mathematics),
+    info("math.OC", "Optimization and Control", Mathematics),
+    info("math.PR", "Probability", Mathematics),
+    info("math.QA", "Quantum Algebra", Mathematics),
+    info("math.RA", "Rings and Algebras", Mathematics),
+    info("math.RT", "Representation Theory", Mathematics),
+    info("math.SG", "Symplectic Geometry", Mathematics),
+    info("math.SP", "Spectral Theory", Mathematics),
+    info("math.ST", "Statistics Theory", Mathematics),
+    info("astro-ph", "Astrophysics", Physics),
+    info("astro-ph.CO", "Cosmology and Nongalactic Astrophysics", Physics),
+    info("astro-ph.EP", "Earth and Planetary Astrophysics", Physics),
+    info("astro-ph.GA", "Astrophysics of Galaxies", Physics),
+    info("astro-ph.HE", "High Energy Astrophysical Phenomena", Physics),
+    info("astro-ph.IM", "Instrumentation and Methods for Astrophysics", Physics),
+    info("astro-ph.SR", "Solar and Stellar Astrophysics", Physics),
+    info("cond-mat.dis-nn", "Disordered Systems and Neural Networks", Physics),
+    info("cond-mat.mes-hall", "Mesoscale and Nanoscale Physics", Physics),
+    info("cond-mat.mtrl-sci", "Materials Science", Physics),
+    info("cond-mat.other", "Other Condensed Matter", Physics),
+    info("cond-mat.quant-gas", "Quantum Gases", Physics),
+    info("cond-mat.soft", "Soft Condensed Matter", Physics),
+    info("cond-mat.stat-mech", "Statistical Mechanics", Physics),
+    info("cond-mat.str-el", "Strongly Correlated Electrons", Physics),
+    info("cond-mat.supr-con", "Superconductivity", Physics),
+    info("gr-qc", "General Relativity and Quantum Cosmology", Physics),
+    info("hep-ex", "High Energy Physics - Experiment", Physics),
+    info("hep-lat", "High Energy Physics - Lattice", Physics),
+    info("hep-ph", "High Energy Physics - Phenomenology", Physics),
+    info("hep-th", "High Energy Physics - Theory", Physics),
+    info("math-ph", "Mathematical Physics", Physics),
+    info("nlin.AO", "Adaptation and Self-Organizing Systems", Physics),
+    info("nlin.CD", "Chaotic Dynamics", Physics),
+    info("nlin.CG", "Cellular Automata and Lattice Gases", Physics),
+    info("nlin.PS", "Pattern Formation and Solitons", Physics),
+    info("nlin.SI", "Exactly Solvable and Integrable Systems", Physics),
+    info("nucl-ex", "Nuclear Experiment", Physics),
+    info("nucl-th", "Nuclear Theory", Physics),
+    info("physics.acc-ph", "Accelerator Physics", Physics),
+    info("physics.ao-ph", "Atmospheric and Oceanic Physics", Physics),
+    info("physics.app-ph", "Applied Physics", Physics),
+    info("physics.atm-clus", "Atomic and Molecular Clusters", Physics),
+    info("physics.atom-ph", "Atomic Physics", Physics),
+    info("physics.bio-ph", "Biological Physics", Physics),
+    info("physics.chem-ph", "Chemical Physics", Physics),
+    info("physics.class-ph", "Classical Physics", Physics),
+    info("physics.comp-ph", "Computational Physics", Physics),
+    info("physics.data-an", "Data Analysis, Statistics and Probability", Physics),
+    info("physics.ed-ph", "Physics Education", Physics),
+    info("physics.flu-dyn", "Fluid Dynamics", Physics),
+    info("physics.gen-ph", "General Physics", Physics),
+    info("physics.geo-ph", "Geophysics", Physics),
+    info("physics.hist-ph", "History and Philosophy of Physics", Physics),
+    info("physics.ins-det", "Instrumentation and Detectors", Physics),
+    info("physics.med-ph", "Medical Physics", Physics),
+    info("physics.optics", "Optics", Physics),
+    info("physics.plasm-ph", "Plasma Physics", Physics),
+    info("physics.pop-ph", "Popular Physics", Physics),
+    info("physics.soc-ph", "Physics and Society", Physics),
+    info("physics.space-ph", "Space Physics", Physics),
+    info("quant-ph", "Quantum Physics", Physics),
+    info("q-bio.BM", "Biomolecules", QuantitativeBiology),
+    info("q-bio.CB", "Cell Behavior", QuantitativeBiology),
+    info("q-bio.GN", "Genomics", QuantitativeBiology),
+    info("q-bio.MN", "Molecular Networks", QuantitativeBiology),
+    info("q-bio.NC", "Neurons and Cognition", QuantitativeBiology),
+    info("q-bio.OT", "Other Quantitative Biology", QuantitativeBiology),
+    info("q-bio.PE", "Populations and Evolution", QuantitativeBiology),
+    info("q-bio.QM", "Quantitative Methods", QuantitativeBiology),
+    info("q-bio.SC", "Subcellular Processes", QuantitativeBiology),
+    info("q-bio.TO", "Tissues and Organs", QuantitativeBiology),
+    info("q-fin.CP", "Computational Finance", QuantitativeFinance),
+    info("q-fin.GN", "General Finance", QuantitativeFinance),
+    info("q-fin.MF", "Mathematical Finance", QuantitativeFinance),
+    info("q-fin.PM", "Portfolio Management", QuantitativeFinance),
+    info("q-fin.PR", "Pricing of Securities", QuantitativeFinance),
+    info("q-fin.RM", "Risk Management", QuantitativeFinance),
+    info("q-fin.ST", "Statistical Finance", QuantitativeFinance),
+    info("q-fin.TR", "Trading and Market Microstructure", QuantitativeFinance),
+    info("stat.AP", "Applications", Statistics),
+    info("stat.CO", "Computation", Statistics),
+    info("stat.ME", "Methodology", Statistics),
+    info("stat.ML", "Machine Learning", Statistics),
+    info("stat.OT", "Other Statistics", Statistics),
+];
+
+/// Alias -> canonical category. Covers the official cross-archive aliases
+/// as well as archives that were merged into a newer one.
+const ALIASES: &[(&str, &str)] = &[
+    ("math.IT", "cs.IT"),
+    ("math.MP", "math-ph"),
+    ("cs.NA", "math.NA"),
+    ("cs.SY", "eess.SY"),
+    ("stat.TH", "math.ST"),
+    ("q-fin.EC", "econ.GN"),
+    ("acc-phys", "physics.acc-ph"),
+    ("adap-org", "nlin.AO"),
+    ("alg-geom", "math.AG"),
+    ("ao-sci", "physics.ao-ph"),
+    ("atom-ph", "physics.atom-ph"),
+    ("bayes-an", "physics.data-an"),
+    ("chao-dyn", "nlin.CD"),
+    ("chem-ph", "physics.chem-ph"),
+    ("cmp-lg", "cs.CL"),
+    ("comp-gas", "nlin.CG"),
+    ("dg-ga", "math.DG"),
+    ("funct-an", "math.FA"),
+    ("mtrl-th", "cond-mat.mtrl-sci"),
+    ("patt-sol", "nlin.PS"),
+    ("plasm-ph", "physics.plasm-ph"),
+    ("q-alg", "math.QA"),
+    ("solv-int", "nlin.SI"),
+    ("supr-con", "cond-mat.supr-con"),
+];
+
+/// Every canonical category of the arXiv taxonomy.
+pub fn taxonomy() -> &'static [CategoryInfo] {
+    TAXONOMY
+}
+
+fn archive_of(id: &str) -> &str {
+    id.split_once('.').map_or(id, |(archive, _)| archive)
+}
+
+fn canonical_id(id: &str) -> &str {
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == id)
+        .map_or(id, |(_, canonical)| canonical)
+}
+
+fn lookup(id: &str) -> Option<&'static CategoryInfo> {
+    let id = canonical_id(id);
+    TAXONOMY.iter().find(|info| info.id == id)
+}
+
+fn is_known_archive(archive: &str) -> bool {
+    TAXONOMY.iter().any(|info| info.archive() == archive)
+        || ALIASES
+            .iter()
+            .any(|(alias, _)| archive_of(alias) == archive)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+
+    prev[b.len()]
+}
+
+fn suggest(id: &str) -> Option<&'static str> {
+    TAXONOMY
+        .iter()
+        .map(|info| info.id)
+        .chain(ALIASES.iter().map(|(alias, _)| *alias))
+        .map(|known| {
+            let distance = if known.eq_ignore_ascii_case(id) {
+                0
+            } else {
+                edit_distance(&known.to_ascii_lowercase(), &id.to_ascii_lowercase()) + 1
+            };
+            (distance, known)
+        })
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// An arXiv subject category such as `cs.AI`.
+///
+/// Categories returned by the API are kept verbatim, so terms outside the
+/// taxonomy (e.g. ACM or MSC classes) are representable; use
+/// [`Category::from_str`] to validate user input.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct Category(String);
+
+impl Category {
+    pub fn new<S: ToString>(term: S) -> Self {
+        Self(term.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn info(&self) -> Option<&'static CategoryInfo> {
+        lookup(&self.0)
+    }
+
+    pub fn is_known(&self) -> bool {
+        self.info().is_some()
+    }
+
+    pub fn is_alias(&self) -> bool {
+        canonical_id(&self.0) != self.0
+    }
+
+    pub fn name(&self) -> Option<&'static str> {
+        self.info().map(|info| info.name)
+    }
+
+    pub fn group(&self) -> Option<CategoryGroup> {
+        self.info().map(|info| info.group)
+    }
+
+    /// The archive part of the category, e.g. `cs` for `cs.AI`.
+    pub fn archive(&self) -> &str {
+        archive_of(&self.0)
+    }
+
+    /// Resolves aliases such as `math.IT` to their canonical category (`cs.IT`).
+    pub fn canonical(&self) -> Category {
+        Category::new(canonical_id(&self.0))
+    }
+
+    /// Alias-aware comparison: `math.IT` is the same category as `cs.IT`.
+    pub fn same_as(&self, other: &Category) -> bool {
+        canonical_id(&self.0) == canonical_id(&other.0)
+    }
+}
+
+impl FromStr for Category {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if lookup(s).is_some() {
+            return Ok(Category::new(s));
+        }
+
+        match suggest(s) {
+            Some(suggestion) => Err(anyhow!(
+                "Unknown arXiv category: {} (did you mean {}?)",
+                s,
+                suggestion
+            )),
+            None => Err(anyhow!("Unknown arXiv category: {}", s)),
+        }
+    }
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for Category {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Category {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Category {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Checks a `cat:` search term against the taxonomy. Archive wildcards such
+/// as `cs.*` are accepted as long as the archive exists.
+pub(crate) fn validate_category_term(term: &str) -> anyhow::Result<()> {
+    if let Some(archive) = term.strip_suffix(".*") {
+        if is_known_archive(archive) {
+            return Ok(());
+        }
+        return Err(anyhow!("Unknown arXiv archive: {}", archive));
+    }
+
+    term.parse::<Category>().map(|_| ())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_known_category() {
+        let category: Category = "cs.AI".parse().unwrap();
+        assert_eq!(category.name(), Some("Artificial Intelligence"));
+        assert_eq!(category.archive(), "cs");
+        assert_eq!(category.group(), Some(CategoryGroup::ComputerScience));
+        assert_eq!(category.group().unwrap().name(), "Computer Science");
+        assert!(!category.is_alias());
+    }
+
+    #[test]
+    fn test_aliases() {
+        let alias: Category = "math.IT".parse().unwrap();
+        assert!(alias.is_alias());
+        assert_eq!(alias.canonical(), "cs.IT");
+        assert_eq!(alias.name(), Some("Information Theory"));
+        assert!(alias.same_as(&Category::new("cs.IT")));
+
+        let legacy: Category = "cmp-lg".parse().unwrap();
+        assert_eq!(legacy.canonical(), "cs.CL");
+    }
+
+    #[test]
+    fn test_unknown_category() {
+        let err = "cs.Al".parse::<Category>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown arXiv category: cs.Al (did you mean cs.AI?)"
+        );
+
+        let category = Category::new("I.2.7");
+        assert!(!category.is_known());
+        assert_eq!(category.name(), None);
+    }
+
+    #[test]
+    fn test_validate_category_term() {
+        assert!(validate_category_term("cs.CL").is_ok());
+        assert!(validate_category_term("cs.*").is_ok());
+        assert!(validate_category_term("foo.*").is_err());
+        assert!(validate_category_term("cs.XX").is_err());
+    }
+
+    #[test]
+    fn test_taxonomy_is_consistent() {
+        for info in taxonomy() {
+            assert_eq!(lookup(info.id), Some(info));
+        }
+        for (alias, canonical) in ALIASES {
+            assert!(lookup(canonical).is_some(), "{} -> {}", alias, canonical);
+            assert!(TAXONOMY.iter().all(|info| info.id != *alias));
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::ArxivResult;
+use crate::withdrawal::mentions_withdrawal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VenueStatus {
+    Accepted,
+    ToAppear,
+    Published,
+    Submitted,
+    Presented,
+}
+
+const VENUE_KEYWORDS: &[(&str, VenueStatus)] = &[
+    ("accepted for publication in", VenueStatus::Accepted),
+    ("accepted for publication at", VenueStatus::Accepted),
+    ("accepted at", VenueStatus::Accepted),
+    ("accepted to", VenueStatus::Accepted),
+    ("accepted by", VenueStatus::Accepted),
+    ("accepted in", VenueStatus::Accepted),
+    ("accepted for", VenueStatus::Accepted),
+    ("to appear in", VenueStatus::ToAppear),
+    ("to appear at", VenueStatus::ToAppear),
+    ("to be published in", VenueStatus::ToAppear),
+    ("published in", VenueStatus::Published),
+    ("submitted to", VenueStatus::Submitted),
+    ("under review at", VenueStatus::Submitted),
+    ("presented at", VenueStatus::Presented),
+];
+
+const CODE_HOSTS: &[&str] = &["github.com", "gitlab.com", "bitbucket.org", "codeberg.org"];
+
+const DATA_HOSTS: &[&str] = &[
+    "zenodo.org",
+    "figshare.com",
+    "kaggle.com",
+    "huggingface.co/datasets",
+    "dataverse",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VenueMention {
+    pub venue: String,
+    pub status: VenueStatus,
+}
+
+/// Best-effort structure extracted from the free-text `comment` field.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentInfo {
+    pub pages: Option<u32>,
+    pub figures: Option<u32>,
+    pub tables: Option<u32>,
+    pub venues: Vec<VenueMention>,
+    pub code_urls: Vec<String>,
+    pub data_urls: Vec<String>,
+    pub other_urls: Vec<String>,
+    /// The withdrawal notice, if the comment announces one.
+    pub withdrawal: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Counted {
+    Pages,
+    Figures,
+    Tables,
+}
+
+fn counted_unit(word: &str) -> Option<Counted> {
+    if word.starts_with("page") || word == "pp" {
+        Some(Counted::Pages)
+    } else if word.starts_with("fig") {
+        Some(Counted::Figures)
+    } else if word.starts_with("table") || word == "tab" || word == "tabs" {
+        Some(Counted::Tables)
+    } else {
+        None
+    }
+}
+
+/// Splits into lowercase runs of letters and runs of digits, so `12pp` and
+/// `12 pages` tokenize alike.
+fn words(text: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut current = String::new();
+
+    for c in text.chars() {
+        let same_kind = current
+            .chars()
+            .last()
+            .is_some_and(|last| last.is_ascii_digit() == c.is_ascii_digit());
+        if !current.is_empty() && (!c.is_alphanumeric() || !same_kind) {
+            words.push(std::mem::take(&mut current));
+        }
+        if c.is_alphanumeric() {
+            current.extend(c.to_lowercase());
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+fn parse_counts(text: &str, info: &mut CommentInfo) {
+    const FILLERS: &[&str] = &["color", "colour", "colored", "coloured", "eps", "main"];
+
+    let words = words(text);
+    for (i, word) in words.iter().enumerate() {
+        let count = if word == "no" {
+            Some(0)
+        } else {
+            word.parse::<u32>().ok()
+        };
+        let Some(count) = count else {
+            continue;
+        };
+
+        let unit = words[i + 1..]
+            .iter()
+            .find(|next| !FILLERS.contains(&next.as_str()))
+            .and_then(|next| counted_unit(next));
+
+        let slot = match unit {
+            Some(Counted::Pages) => &mut info.pages,
+            Some(Counted::Figures) => &mut info.figures,
+            Some(Counted::Tables) => &mut info.tables,
+            None => continue,
+        };
+        if slot.is_none() {
+            *slot = Some(count);
+        }
+    }
+}
+
+fn find_urls(text: &str) -> Vec<(usize, String)> {
+    let mut urls = vec![];
+    let mut offset = 0;
+
+    while let Some(pos) = text[offset..].find("http") {
+        let start = offset + pos;
+        let rest = &text[start..];
+        if !rest.starts_with("http://") && !rest.starts_with("https://") {
+            offset = start + 4;
+            continue;
+        }
+
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == '"' || c == '<' || c == '>')
+            .unwrap_or(rest.len());
+        let url = rest[..end].trim_end_matches(['.', ',', ';', ':', ')', ']']);
+        urls.push((start, url.to_string()));
+        offset = start + end;
+    }
+
+    urls
+}
+
+fn classify_urls(comment: &str, info: &mut CommentInfo) {
+    for (start, url) in find_urls(comment) {
+        let lower_url = url.to_lowercase();
+        let context_start = comment[..start]
+            .rfind([';', ',', '.', '('])
+            .map_or(0, |pos| pos + 1);
+        let context = comment[context_start..start].to_lowercase();
+
+        let data_context = context.contains("data") || context.contains("benchmark");
+        let code_context = context.contains("code")
+            || context.contains("implementation")
+            || context.contains("software");
+
+        if DATA_HOSTS.iter().any(|host| lower_url.contains(host)) {
+            info.data_urls.push(url);
+        } else if CODE_HOSTS.iter().any(|host| lower_url.contains(host)) {
+            info.code_urls.push(url);
+        } else if data_context {
+            info.data_urls.push(url);
+        } else if code_context {
+            info.code_urls.push(url);
+        } else {
+            info.other_urls.push(url);
+        }
+    }
+}
+
+/// Whether `word` looks like an abbreviation in a venue name (`Proc`, `Conf`,
+/// `Phys`, `J`) rather than the last word of a sentence.
+fn is_abbreviation(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && word.len() <= 5
+        && chars.all(|c| c.is_ascii_lowercase())
+}
+
+/// Splits on `;`, `,`, newlines and sentence-ending `. `.
+fn venue_segments(comment: &str) -> Vec<&str> {
+    let mut segments = vec![];
+    for part in comment.split([';', ',', '\n']) {
+        let mut start = 0;
+        for (pos, _) in part.match_indices(". ") {
+            let word = part[start..pos].split_whitespace().last().unwrap_or("");
+            if !is_abbreviation(word) {
+                segments.push(&part[start..pos]);
+                start = pos + 2;
+            }
+        }
+        segments.push(&part[start..]);
+    }
+    segments
+}
+
+fn parse_venues(comment: &str, info: &mut CommentInfo) {
+    for segment in venue_segments(comment) {
+        let lower = segment.to_ascii_lowercase();
+        let found = VENUE_KEYWORDS
+            .iter()
+            .filter_map(|(keyword, status)| lower.find(keyword).map(|pos| (pos, keyword, status)))
+            .min_by_key(|(pos, keyword, _)| (*pos, usize::MAX - keyword.len()));
+
+        if let Some((pos, keyword, status)) = found {
+            let venue = segment[pos + keyword.len()..]
+                .trim()
+                .trim_start_matches("the ")
+                .trim_end_matches('.')
+                .trim();
+            if !venue.is_empty() && !venue.starts_with("http") {
+                info.venues.push(VenueMention {
+                    venue: venue.to_string(),
+                    status: *status,
+                });
+            }
+        }
+    }
+}
+
+fn parse_withdrawal(comment: &str, info: &mut CommentInfo) {
+    if mentions_withdrawal(comment) {
+        info.withdrawal = Some(comment.trim().to_string());
+    }
+}
+
+impl CommentInfo {
+    pub fn parse(comment: &str) -> Self {
+        let mut info = CommentInfo::default();
+        let without_urls = find_urls(comment)
+            .iter()
+            .fold(comment.to_string(), |text, (_, url)| text.replace(url, " "));
+
+        parse_counts(&without_urls, &mut info);
+        parse_venues(&without_urls, &mut info);
+        classify_urls(comment, &mut info);
+        parse_withdrawal(comment, &mut info);
+
+        info
+    }
+
+    pub fn is_withdrawn(&self) -> bool {
+        self.withdrawal.is_some()
+    }
+}
+
+impl ArxivResult {
+    pub fn comment_info(&self) -> Option<CommentInfo> {
+        self.comment.as_deref().map(CommentInfo::parse)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_counts() {
+        let info = CommentInfo::parse("12 pages, 5 figures, 3 tables");
+        assert_eq!(info.pages, Some(12));
+        assert_eq!(info.figures, Some(5));
+        assert_eq!(info.tables, Some(3));
+
+        let info = CommentInfo::parse("RevTeX, 4pp, 2 colour figures; no tables");
+        assert_eq!(info.pages, Some(4));
+        assert_eq!(info.figures, Some(2));
+        assert_eq!(info.tables, Some(0));
+    }
+
+    #[test]
+    fn test_venues_and_urls() {
+        let info = CommentInfo::parse(
+            "12 pages, 5 figures, accepted at NeurIPS 2023; code at https://github.com/foo/bar. \
+             Dataset: https://zenodo.org/record/123",
+        );
+        assert_eq!(
+            info.venues,
+            vec![VenueMention {
+                venue: "NeurIPS 2023".to_string(),
+                status: VenueStatus::Accepted,
+            }]
+        );
+        assert_eq!(info.code_urls, vec!["https://github.com/foo/bar"]);
+        assert_eq!(info.data_urls, vec!["https://zenodo.org/record/123"]);
+        assert!(info.other_urls.is_empty());
+        assert!(!info.is_withdrawn());
+    }
+
+    #[test]
+    fn test_venue_sentences() {
+        let info = CommentInfo::parse("Accepted to ICML 2024. 9 pages");
+        assert_eq!(info.venues[0].venue, "ICML 2024");
+        assert_eq!(info.pages, Some(9));
+
+        let info = CommentInfo::parse("To appear in Proc. Int. Conf. on Learning. Code soon");
+        assert_eq!(info.venues[0].venue, "Proc. Int. Conf. on Learning");
+    }
+
+    #[test]
+    fn test_withdrawal() {
+        let info = CommentInfo::parse("This paper has been withdrawn by the author");
+        assert!(info.is_withdrawn());
+        assert_eq!(info.pages, None);
+    }
+
+    #[test]
+    fn test_comment_info() {
+        let results = fixture();
+        let info = results[0].comment_info().unwrap();
+        assert_eq!(info.pages, Some(12));
+        assert_eq!(info.venues[0].venue, "ACL 2024 Findings");
+        assert_eq!(info.venues[0].status, VenueStatus::Accepted);
+        assert_eq!(
+            info.code_urls,
+            vec!["https://github.com/phycholosogy/RAG-privacy"]
+        );
+
+        assert_eq!(results[1].comment_info().unwrap().figures, Some(0));
+        assert!(results[2].comment_info().unwrap().is_withdrawn());
+    }
+}
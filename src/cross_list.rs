@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+use crate::category::Category;
+use crate::models::ArxivResult;
+
+/// How a paper is listed in a category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CrossListStatus {
+    /// The category is the paper's primary category.
+    Primary,
+    /// The paper is only cross-listed in the category.
+    CrossList,
+    /// The paper does not appear in the category at all.
+    NotListed,
+}
+
+/// `wanted` may be a full category (`cs.CL`) or a bare archive (`hep-th`, `cs`).
+fn listed_in(listed: &Category, wanted: &Category) -> bool {
+    listed.same_as(wanted)
+        || (!wanted.as_str().contains('.') && listed.canonical().archive() == wanted.as_str())
+}
+
+impl ArxivResult {
+    pub fn cross_list_status(&self, category: &Category) -> CrossListStatus {
+        // An old-style id names the archive the paper was submitted to, which
+        // only stands in for a missing primary category.
+        let primary = if self.primary_category.as_str().is_empty() {
+            self.arxiv_id().and_then(|id| id.implied_category())
+        } else {
+            Some(self.primary_category.clone())
+        };
+
+        if primary.is_some_and(|primary| listed_in(&primary, category)) {
+            CrossListStatus::Primary
+        } else if self
+            .categories
+            .iter()
+            .any(|listed| listed_in(listed, category))
+        {
+            CrossListStatus::CrossList
+        } else {
+            CrossListStatus::NotListed
+        }
+    }
+
+    pub fn is_primary_in(&self, category: &Category) -> bool {
+        self.cross_list_status(category) == CrossListStatus::Primary
+    }
+
+    pub fn is_cross_list_for(&self, category: &Category) -> bool {
+        self.cross_list_status(category) == CrossListStatus::CrossList
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_cross_list_status() {
+        let results = fixture();
+        let cs_cl = Category::new("cs.CL");
+
+        assert_eq!(
+            results[0].cross_list_status(&cs_cl),
+            CrossListStatus::CrossList
+        );
+        assert!(results[0].is_cross_list_for(&cs_cl));
+        assert!(results[0].is_primary_in(&Category::new("cs.CR")));
+        assert!(results[0].is_primary_in(&Category::new("cs")));
+        assert_eq!(
+            results[1].cross_list_status(&cs_cl),
+            CrossListStatus::NotListed
+        );
+    }
+
+    #[test]
+    fn test_old_style_id_implies_primary() {
+        let mut result = fixture().remove(1);
+        assert!(result.is_cross_list_for(&Category::new("gr-qc")));
+
+        result.primary_category = Category::new("gr-qc");
+        assert!(result.is_primary_in(&Category::new("gr-qc")));
+        assert!(result.is_cross_list_for(&Category::new("hep-th")));
+
+        result.primary_category = Category::new("");
+        assert!(result.is_primary_in(&Category::new("hep-th")));
+    }
+
+    #[test]
+    fn test_alias_aware() {
+        let mut result = fixture().remove(0);
+        result.categories.push(Category::new("math.IT"));
+        assert!(result.is_cross_list_for(&Category::new("cs.IT")));
+    }
+}
@@ -0,0 +1,291 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::category::Category;
+use crate::export::plain;
+use crate::models::ArxivResult;
+
+/// A field whose value differs between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange<T> {
+    pub from: T,
+    pub to: T,
+}
+
+/// A run of consecutive words in a word-level diff.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", content = "text", rename_all = "snake_case")]
+pub enum WordChange {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+/// What changed between two snapshots of a paper, typically two versions.
+/// Whitespace-only differences are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultDiff {
+    pub title: Option<FieldChange<String>>,
+    /// Word-level diff of the abstract, if it changed.
+    pub summary: Option<Vec<WordChange>>,
+    pub authors_added: Vec<String>,
+    pub authors_removed: Vec<String>,
+    pub primary_category: Option<FieldChange<Category>>,
+    pub categories_added: Vec<Category>,
+    pub categories_removed: Vec<Category>,
+    pub comment: Option<FieldChange<Option<String>>>,
+    pub doi: Option<FieldChange<Option<String>>>,
+    pub journal_ref: Option<FieldChange<Option<String>>>,
+}
+
+fn changed<T: PartialEq + Clone>(from: &T, to: &T) -> Option<FieldChange<T>> {
+    (from != to).then(|| FieldChange {
+        from: from.clone(),
+        to: to.clone(),
+    })
+}
+
+fn changed_text(from: &Option<String>, to: &Option<String>) -> Option<FieldChange<Option<String>>> {
+    (from.as_deref().map(plain) != to.as_deref().map(plain)).then(|| FieldChange {
+        from: from.clone(),
+        to: to.clone(),
+    })
+}
+
+/// Items of `to` that are not in `from`.
+fn added<T: PartialEq + Clone>(from: &[T], to: &[T]) -> Vec<T> {
+    to.iter()
+        .filter(|item| !from.contains(item))
+        .cloned()
+        .collect()
+}
+
+fn push_word(changes: &mut Vec<WordChange>, change: WordChange) {
+    let merged = match (changes.last_mut(), &change) {
+        (Some(WordChange::Same(run)), WordChange::Same(word))
+        | (Some(WordChange::Added(run)), WordChange::Added(word))
+        | (Some(WordChange::Removed(run)), WordChange::Removed(word)) => {
+            run.push(' ');
+            run.push_str(word);
+            true
+        }
+        _ => false,
+    };
+    if !merged {
+        changes.push(change);
+    }
+}
+
+/// Diffs the words of two texts via their longest common subsequence. In a
+/// replacement the removed words come first.
+pub fn word_diff(from: &str, to: &str) -> Vec<WordChange> {
+    let from = from.split_whitespace().collect::<Vec<_>>();
+    let to = to.split_whitespace().collect::<Vec<_>>();
+
+    // lcs[i][j] is the LCS length of from[i..] and to[j..].
+    let mut lcs = vec![vec![0usize; to.len() + 1]; from.len() + 1];
+    for i in (0..from.len()).rev() {
+        for j in (0..to.len()).rev() {
+            lcs[i][j] = if from[i] == to[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < from.len() || j < to.len() {
+        let change = if i < from.len() && j < to.len() && from[i] == to[j] {
+            i += 1;
+            j += 1;
+            WordChange::Same(from[i - 1].to_string())
+        } else if j < to.len() && (i == from.len() || lcs[i][j + 1] > lcs[i + 1][j]) {
+            j += 1;
+            WordChange::Added(to[j - 1].to_string())
+        } else {
+            i += 1;
+            WordChange::Removed(from[i - 1].to_string())
+        };
+        push_word(&mut changes, change);
+    }
+
+    changes
+}
+
+impl ResultDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn write_optional(
+    f: &mut std::fmt::Formatter<'_>,
+    label: &str,
+    change: &Option<FieldChange<Option<String>>>,
+) -> std::fmt::Result {
+    if let Some(FieldChange { from, to }) = change {
+        writeln!(
+            f,
+            "{}: {} -> {}",
+            label,
+            from.as_deref().unwrap_or("(none)"),
+            to.as_deref().unwrap_or("(none)")
+        )?;
+    }
+    Ok(())
+}
+
+fn write_list<T: Display>(
+    f: &mut std::fmt::Formatter<'_>,
+    label: &str,
+    added: &[T],
+    removed: &[T],
+) -> std::fmt::Result {
+    if added.is_empty() && removed.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "{}:", label)?;
+    for item in added {
+        writeln!(f, "  + {}", item)?;
+    }
+    for item in removed {
+        writeln!(f, "  - {}", item)?;
+    }
+    Ok(())
+}
+
+/// Renders one line per changed field; the abstract uses `[-removed-]` and
+/// `{+added+}` markers.
+impl Display for ResultDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(FieldChange { from, to }) = &self.title {
+            writeln!(f, "title: {} -> {}", plain(from), plain(to))?;
+        }
+        if let Some(summary) = &self.summary {
+            let words = summary
+                .iter()
+                .map(|change| match change {
+                    WordChange::Same(text) => text.clone(),
+                    WordChange::Added(text) => format!("{{+{}+}}", text),
+                    WordChange::Removed(text) => format!("[-{}-]", text),
+                })
+                .collect::<Vec<_>>();
+            writeln!(f, "abstract: {}", words.join(" "))?;
+        }
+        write_list(f, "authors", &self.authors_added, &self.authors_removed)?;
+        if let Some(FieldChange { from, to }) = &self.primary_category {
+            writeln!(f, "primary category: {} -> {}", from, to)?;
+        }
+        write_list(
+            f,
+            "categories",
+            &self.categories_added,
+            &self.categories_removed,
+        )?;
+        write_optional(f, "comment", &self.comment)?;
+        write_optional(f, "doi", &self.doi)?;
+        write_optional(f, "journal_ref", &self.journal_ref)
+    }
+}
+
+impl ArxivResult {
+    /// What changed from `self` to `other`.
+    pub fn diff(&self, other: &ArxivResult) -> ResultDiff {
+        let summary_changed = plain(&self.summary) != plain(&other.summary);
+
+        ResultDiff {
+            title: (plain(&self.title) != plain(&other.title)).then(|| FieldChange {
+                from: self.title.clone(),
+                to: other.title.clone(),
+            }),
+            summary: summary_changed.then(|| word_diff(&self.summary, &other.summary)),
+            authors_added: added(&self.authors, &other.authors),
+            authors_removed: added(&other.authors, &self.authors),
+            primary_category: changed(&self.primary_category, &other.primary_category),
+            categories_added: added(&self.categories, &other.categories),
+            categories_removed: added(&other.categories, &self.categories),
+            comment: changed_text(&self.comment, &other.comment),
+            doi: changed_text(&self.doi, &other.doi),
+            journal_ref: changed_text(&self.journal_ref, &other.journal_ref),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_word_diff() {
+        assert_eq!(
+            word_diff("we study the large model", "we study  a small model today"),
+            vec![
+                WordChange::Same("we study".to_string()),
+                WordChange::Removed("the large".to_string()),
+                WordChange::Added("a small".to_string()),
+                WordChange::Same("model".to_string()),
+                WordChange::Added("today".to_string()),
+            ]
+        );
+        assert!(word_diff("", "").is_empty());
+    }
+
+    #[test]
+    fn test_word_diff_replacement() {
+        assert_eq!(
+            word_diff("graph neural networks", "transformers"),
+            vec![
+                WordChange::Removed("graph neural networks".to_string()),
+                WordChange::Added("transformers".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = fixture()[0].clone();
+        let mut new = old.clone();
+        new.title = new.title.replace('\n', " ");
+        new.summary = format!("{} We release our code.", new.summary);
+        new.authors.remove(0);
+        new.authors.push("Jane Doe".to_string());
+        new.categories.push(Category::new("cs.LG"));
+        new.doi = Some("10.1000/xyz".to_string());
+
+        assert!(old.diff(&old).is_empty());
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.title, None);
+        let summary = diff.summary.as_ref().unwrap();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(
+            summary[1],
+            WordChange::Added("We release our code.".to_string())
+        );
+        assert_eq!(diff.authors_added, vec!["Jane Doe"]);
+        assert_eq!(diff.authors_removed, vec![old.authors[0].clone()]);
+        assert_eq!(diff.categories_added, vec![Category::new("cs.LG")]);
+        assert_eq!(
+            diff.doi,
+            Some(FieldChange {
+                from: None,
+                to: Some("10.1000/xyz".to_string())
+            })
+        );
+        assert_eq!(diff.journal_ref, None);
+
+        let text = diff.to_string();
+        assert!(text.contains("  + Jane Doe\n"));
+        assert!(text.contains("  + cs.LG\n"));
+        assert!(text.contains("doi: (none) -> 10.1000/xyz\n"));
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["doi"]["to"], "10.1000/xyz");
+        let parsed: ResultDiff = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, diff);
+    }
+}
@@ -0,0 +1,80 @@
+mod bibtex;
+#[cfg(feature = "arrow")]
+mod columnar;
+mod csl;
+mod digest;
+mod feed;
+mod jsonld;
+mod note;
+mod ris;
+mod table;
+
+pub use bibtex::{BibtexWriter, CitationKey};
+#[cfg(feature = "arrow")]
+pub use columnar::{table_schema, to_record_batch, ParquetWriter};
+pub use csl::{to_csl_json, CslDate, CslItem, CslName};
+pub use digest::{Digest, DigestGrouping};
+pub use feed::{to_atom_feed, to_json_feed, to_rss_feed, FeedMetadata};
+pub use jsonld::to_json_ld_graph;
+pub use note::{NoteVault, DEFAULT_NOTE_MARKER};
+pub use ris::write_ris;
+pub use table::{CsvWriter, JsonLinesWriter, TableRow, TABLE_COLUMNS};
+
+/// Lowercase name particles that belong to the family name.
+const PARTICLES: &[&str] = &[
+    "van", "von", "der", "den", "de", "del", "della", "di", "da", "du", "la", "le", "dos", "das",
+    "ter", "ten", "zu", "bin", "al",
+];
+
+/// A person's name split into given and family parts, as most citation
+/// formats want it. arXiv only provides names as free text, so the last word
+/// (with any particles like `van` before it) is taken as the family name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PersonName {
+    pub(crate) given: String,
+    pub(crate) family: String,
+}
+
+/// Collapses the line breaks arXiv keeps in titles and abstracts.
+pub(crate) fn plain(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub(crate) fn split_name(name: &str) -> PersonName {
+    let words = name.split_whitespace().collect::<Vec<_>>();
+    let Some(last) = words.len().checked_sub(1) else {
+        return PersonName {
+            given: String::new(),
+            family: String::new(),
+        };
+    };
+
+    let mut start = last;
+    while start > 1 && PARTICLES.contains(&words[start - 1]) {
+        start -= 1;
+    }
+
+    PersonName {
+        given: words[..start].join(" "),
+        family: words[start..].join(" "),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split_name() {
+        let name = split_name("Pengfei He");
+        assert_eq!(name.given, "Pengfei");
+        assert_eq!(name.family, "He");
+
+        let name = split_name("Ludwig van der Berg");
+        assert_eq!(name.given, "Ludwig");
+        assert_eq!(name.family, "van der Berg");
+
+        assert_eq!(split_name("Plato").family, "Plato");
+        assert_eq!(split_name("Plato").given, "");
+    }
+}
@@ -0,0 +1,390 @@
+use std::collections::HashSet;
+use std::io::Write;
+
+use crate::export::{plain, split_name};
+use crate::models::ArxivResult;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Title words skipped when picking the keyword of a citation key.
+const STOPWORDS: &[&str] = &[
+    "a", "an", "the", "on", "of", "in", "for", "and", "to", "with", "from", "by", "at", "via",
+    "towards", "toward", "is", "are", "how", "what", "why", "when", "do", "does",
+];
+
+/// How citation keys are built.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CitationKey {
+    /// First author's family name, year and first significant title word,
+    /// e.g. `zeng2024good`.
+    #[default]
+    AuthorYearTitle,
+    /// First author's family name and year, e.g. `zeng2024`.
+    AuthorYear,
+    /// The arXiv identifier, e.g. `arXiv:2402.16893`.
+    ArxivId,
+}
+
+/// The ASCII letters of a lowercase Latin letter with diacritics.
+fn fold_diacritic(c: char) -> Option<&'static str> {
+    let folded = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => "e",
+        'ğ' => "g",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => "i",
+        'ł' => "l",
+        'ñ' | 'ń' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'œ' => "oe",
+        'ř' => "r",
+        'ś' | 'š' | 'ş' | 'ș' => "s",
+        'ß' => "ss",
+        'ť' | 'ţ' | 'ț' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => "u",
+        'ý' | 'ÿ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+fn key_part(text: &str) -> String {
+    let mut part = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() {
+            part.push(c);
+        } else if let Some(folded) = fold_diacritic(c) {
+            part.push_str(folded);
+        }
+    }
+    part
+}
+
+/// Escapes the characters TeX chokes on outside of `$...$` math, and braces
+/// words with inner capitals (`RAG`, `BERT`) so styles do not lowercase them.
+/// Existing TeX commands such as `\"o` are left alone, and a `$` without a
+/// closing one is escaped.
+fn escape_latex(text: &str) -> String {
+    let balanced = text.matches('{').count() == text.matches('}').count();
+    let n_dollars = text.matches('$').count() - text.matches("\\$").count();
+    let mut n_seen = 0;
+    let mut in_math = false;
+
+    text.split_whitespace()
+        .map(|word| {
+            let mut escaped = String::new();
+            let mut prev = None;
+            for c in word.chars() {
+                match c {
+                    '$' if prev != Some('\\') => {
+                        n_seen += 1;
+                        if in_math || n_seen < n_dollars {
+                            in_math = !in_math;
+                        } else {
+                            escaped.push('\\');
+                        }
+                    }
+                    '&' | '%' | '#' | '_' if !in_math && prev != Some('\\') => escaped.push('\\'),
+                    '{' | '}' if !balanced => escaped.push('\\'),
+                    _ => {}
+                }
+                escaped.push(c);
+                prev = Some(c);
+            }
+
+            let is_plain = !word.contains(['$', '\\', '{', '}']);
+            if !is_plain || !word.chars().skip(1).any(|c| c.is_uppercase()) {
+                return escaped;
+            }
+            // Keep surrounding punctuation outside the braces: `({RAG})`.
+            match (
+                escaped.find(char::is_alphanumeric),
+                escaped.rfind(char::is_alphanumeric),
+            ) {
+                (Some(start), Some(end)) => {
+                    let end = end + escaped[end..].chars().next().map_or(0, char::len_utf8);
+                    format!(
+                        "{}{{{}}}{}",
+                        &escaped[..start],
+                        &escaped[start..end],
+                        &escaped[end..]
+                    )
+                }
+                _ => escaped,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl ArxivResult {
+    pub fn citation_key(&self, scheme: CitationKey) -> String {
+        let family = self
+            .authors
+            .first()
+            .map(|author| key_part(&split_name(author).family))
+            .filter(|family| !family.is_empty())
+            .unwrap_or_else(|| "anon".to_string());
+        let year = self.published.year();
+
+        match scheme {
+            CitationKey::AuthorYearTitle => {
+                let word = self
+                    .title
+                    .split(|c: char| c.is_whitespace() || c == '-' || c == ':')
+                    .map(key_part)
+                    .find(|word| !word.is_empty() && !STOPWORDS.contains(&word.as_str()))
+                    .unwrap_or_default();
+                format!("{}{}{}", family, year, word)
+            }
+            CitationKey::AuthorYear => format!("{}{}", family, year),
+            CitationKey::ArxivId => match self.arxiv_id() {
+                Some(id) => format!("arXiv:{}", id.base()),
+                None => format!("{}{}", family, year),
+            },
+        }
+    }
+
+    /// A BibTeX entry keyed with [`CitationKey::AuthorYearTitle`]. Use
+    /// [`BibtexWriter`] to export several results with unique keys.
+    pub fn to_bibtex(&self) -> String {
+        self.to_bibtex_with_key(&self.citation_key(CitationKey::default()))
+    }
+
+    /// An `@article` if the journal reference names a journal, `@misc`
+    /// otherwise.
+    pub fn to_bibtex_with_key(&self, key: &str) -> String {
+        let journal_ref = self.journal_ref_info();
+        let mut fields: Vec<(&str, String)> = vec![];
+
+        fields.push(("title", format!("{{{}}}", escape_latex(&self.title))));
+        let authors = self
+            .authors
+            .iter()
+            .map(|author| {
+                let name = split_name(author);
+                if name.given.is_empty() {
+                    escape_latex(&name.family)
+                } else {
+                    escape_latex(&format!("{}, {}", name.family, name.given))
+                }
+            })
+            .collect::<Vec<_>>();
+        if !authors.is_empty() {
+            fields.push(("author", format!("{{{}}}", authors.join(" and "))));
+        }
+
+        if let Some(journal_ref) = &journal_ref {
+            if let Some(journal) = &journal_ref.journal {
+                fields.push(("journal", format!("{{{}}}", escape_latex(journal))));
+            }
+            if let Some(volume) = &journal_ref.volume {
+                fields.push(("volume", format!("{{{}}}", volume)));
+            }
+            if let Some(issue) = &journal_ref.issue {
+                fields.push(("number", format!("{{{}}}", issue)));
+            }
+            if let Some(pages) = &journal_ref.pages {
+                fields.push(("pages", format!("{{{}}}", pages.replace('-', "--"))));
+            }
+        }
+
+        let year = journal_ref
+            .as_ref()
+            .and_then(|journal_ref| journal_ref.year)
+            .unwrap_or(self.published.year());
+        fields.push(("year", format!("{{{}}}", year)));
+        if year == self.published.year() {
+            let month = MONTHS[self.published.month() as usize - 1];
+            fields.push(("month", month.to_string()));
+        }
+
+        if let Some(id) = self.arxiv_id() {
+            fields.push(("eprint", format!("{{{}}}", id.base())));
+            fields.push(("archivePrefix", "{arXiv}".to_string()));
+        }
+        fields.push(("primaryClass", format!("{{{}}}", self.primary_category)));
+        if let Some(doi) = &self.doi {
+            fields.push(("doi", format!("{{{}}}", doi.trim())));
+        }
+        fields.push(("url", format!("{{{}}}", self.id)));
+        let is_article = journal_ref
+            .as_ref()
+            .is_some_and(|journal_ref| journal_ref.journal.is_some());
+        if !is_article {
+            let note = journal_ref
+                .as_ref()
+                .map(|journal_ref| &journal_ref.raw)
+                .or(self.comment.as_ref());
+            if let Some(note) = note {
+                fields.push(("note", format!("{{{}}}", escape_latex(&plain(note)))));
+            }
+        }
+
+        let entry_type = if is_article { "article" } else { "misc" };
+        let mut entry = format!("@{}{{{},\n", entry_type, key);
+        for (name, value) in fields {
+            entry.push_str(&format!("  {} = {},\n", name, value));
+        }
+        entry.push_str("}\n");
+
+        entry
+    }
+}
+
+/// Writes BibTeX entries, disambiguating clashing citation keys with a
+/// letter suffix (`zeng2024good`, `zeng2024goodb`, ...).
+pub struct BibtexWriter<W> {
+    writer: W,
+    key_scheme: CitationKey,
+    keys: HashSet<String>,
+}
+
+impl<W: Write> BibtexWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            key_scheme: CitationKey::default(),
+            keys: HashSet::new(),
+        }
+    }
+
+    pub fn with_key_scheme(mut self, key_scheme: CitationKey) -> Self {
+        self.key_scheme = key_scheme;
+        self
+    }
+
+    fn unique_key(&mut self, key: String) -> String {
+        let mut candidate = key.clone();
+        let mut n = 0;
+        while !self.keys.insert(candidate.clone()) {
+            n += 1;
+            candidate = match n {
+                1..=25 => format!("{}{}", key, (b'a' + n as u8) as char),
+                _ => format!("{}-{}", key, n + 1),
+            };
+        }
+        candidate
+    }
+
+    /// Writes one entry and returns the key it was given.
+    pub fn write(&mut self, result: &ArxivResult) -> anyhow::Result<String> {
+        let key = self.unique_key(result.citation_key(self.key_scheme));
+        if self.keys.len() > 1 {
+            writeln!(self.writer)?;
+        }
+        self.writer
+            .write_all(result.to_bibtex_with_key(&key).as_bytes())?;
+
+        Ok(key)
+    }
+
+    pub fn write_all<'a>(
+        &mut self,
+        results: impl IntoIterator<Item = &'a ArxivResult>,
+    ) -> anyhow::Result<Vec<String>> {
+        results
+            .into_iter()
+            .map(|result| self.write(result))
+            .collect()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_citation_key() {
+        let results = fixture();
+        assert_eq!(
+            results[0].citation_key(CitationKey::AuthorYearTitle),
+            "zeng2024good"
+        );
+        assert_eq!(results[0].citation_key(CitationKey::AuthorYear), "zeng2024");
+        assert_eq!(
+            results[1].citation_key(CitationKey::ArxivId),
+            "arXiv:hep-th/9901001"
+        );
+
+        let mut result = results[0].clone();
+        result.authors[0] = "Jürgen Müller".to_string();
+        result.title = "Économie de la Réforme".to_string();
+        assert_eq!(
+            result.citation_key(CitationKey::AuthorYearTitle),
+            "muller2024economie"
+        );
+    }
+
+    #[test]
+    fn test_escape_latex() {
+        assert_eq!(
+            escape_latex("Q&A for 100% of\n  RAG_models"),
+            "{Q\\&A} for 100\\% of {RAG\\_models}"
+        );
+        assert_eq!(escape_latex("$O(n_1)$ bounds"), "$O(n_1)$ bounds");
+        assert_eq!(
+            escape_latex("$k$-means for 5$ & 100% of a_b"),
+            "$k$-means for 5\\$ \\& 100\\% of a\\_b"
+        );
+        assert_eq!(
+            escape_latex("Schr\\\"odinger & co"),
+            "Schr\\\"odinger \\& co"
+        );
+        assert_eq!(escape_latex("a } b"), "a \\} b");
+    }
+
+    #[test]
+    fn test_to_bibtex() {
+        let results = fixture();
+
+        let misc = results[0].to_bibtex();
+        assert!(misc.starts_with("@misc{zeng2024good,\n"));
+        assert!(misc.contains("  title = {The Good and The Bad: Exploring Privacy Issues in {Retrieval-Augmented} Generation ({RAG})},\n"));
+        assert!(misc.contains("  author = {Zeng, Shenglai and "));
+        assert!(misc.contains("  month = feb,\n"));
+        assert!(misc.contains(
+            "  eprint = {2402.16893},\n  archivePrefix = {arXiv},\n  primaryClass = {cs.CR},\n"
+        ));
+
+        let article = results[1].to_bibtex();
+        assert!(article.starts_with("@article{"));
+        assert!(article.contains("  journal = {Phys. Rev. Lett.},\n"));
+        assert!(article.contains("  volume = {72},\n"));
+        assert!(article.contains("  year = {1994},\n"));
+        assert!(!article.contains("month"));
+        assert!(article.contains("  doi = {10.1103/PhysRevLett.72.957},\n"));
+        assert!(article.ends_with("}\n"));
+
+        let mut result = results[1].clone();
+        result.journal_ref = Some("vol. 12, pp. 45-60, 2018".to_string());
+        result.authors[0] = "Jos\\'e M_1 & Co".to_string();
+        let misc = result.to_bibtex();
+        assert!(misc.starts_with("@misc{"));
+        assert!(misc.contains("  author = {Co, Jos\\'e M\\_1 \\& and Teitelboim"));
+        assert!(misc.contains("  note = {vol. 12, pp. 45-60, 2018},\n"));
+    }
+
+    #[test]
+    fn test_unique_keys() {
+        let result = &fixture()[0];
+        let mut writer = BibtexWriter::new(vec![]).with_key_scheme(CitationKey::AuthorYear);
+
+        let keys = writer.write_all([result, result, result]).unwrap();
+        assert_eq!(keys, vec!["zeng2024", "zeng2024b", "zeng2024c"]);
+
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(output.matches("@misc{").count(), 3);
+        assert!(output.contains("}\n\n@misc{zeng2024b,\n"));
+    }
+}
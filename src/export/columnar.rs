@@ -0,0 +1,165 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, ListBuilder, RecordBatch, StringArray, StringBuilder, TimestampMillisecondArray,
+    UInt32Array,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use time::OffsetDateTime;
+
+use crate::export::table::TableRow;
+use crate::models::ArxivResult;
+
+fn list_type() -> DataType {
+    DataType::List(Arc::new(Field::new("item", DataType::Utf8, true)))
+}
+
+fn timestamp_type() -> DataType {
+    DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+}
+
+/// The Arrow schema of [`crate::TABLE_COLUMNS`]: authors and categories are
+/// list columns and dates are UTC timestamps.
+pub fn table_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("arxiv_id", DataType::Utf8, false),
+        Field::new("version", DataType::UInt32, true),
+        Field::new("title", DataType::Utf8, false),
+        Field::new("authors", list_type(), false),
+        Field::new("summary", DataType::Utf8, false),
+        Field::new("primary_category", DataType::Utf8, false),
+        Field::new("categories", list_type(), false),
+        Field::new("published", timestamp_type(), false),
+        Field::new("updated", timestamp_type(), false),
+        Field::new("doi", DataType::Utf8, true),
+        Field::new("journal_ref", DataType::Utf8, true),
+        Field::new("comment", DataType::Utf8, true),
+        Field::new("pdf_url", DataType::Utf8, true),
+        Field::new("url", DataType::Utf8, false),
+    ]))
+}
+
+fn strings<'a>(values: impl Iterator<Item = Option<&'a str>>) -> ArrayRef {
+    Arc::new(values.collect::<StringArray>())
+}
+
+fn lists<'a>(values: impl Iterator<Item = &'a Vec<String>>) -> ArrayRef {
+    let mut builder = ListBuilder::new(StringBuilder::new());
+    for list in values {
+        for value in list {
+            builder.values().append_value(value);
+        }
+        builder.append(true);
+    }
+    Arc::new(builder.finish())
+}
+
+fn timestamps(values: impl Iterator<Item = OffsetDateTime>) -> ArrayRef {
+    let millis = values
+        .map(|timestamp| (timestamp.unix_timestamp_nanos() / 1_000_000) as i64)
+        .collect::<Vec<_>>();
+    Arc::new(TimestampMillisecondArray::from(millis).with_timezone("UTC"))
+}
+
+pub fn to_record_batch(results: &[ArxivResult]) -> anyhow::Result<RecordBatch> {
+    let rows = results
+        .iter()
+        .map(TableRow::from_result)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let columns: Vec<ArrayRef> = vec![
+        strings(rows.iter().map(|row| Some(row.arxiv_id.as_str()))),
+        Arc::new(rows.iter().map(|row| row.version).collect::<UInt32Array>()),
+        strings(rows.iter().map(|row| Some(row.title.as_str()))),
+        lists(rows.iter().map(|row| &row.authors)),
+        strings(rows.iter().map(|row| Some(row.summary.as_str()))),
+        strings(rows.iter().map(|row| Some(row.primary_category.as_str()))),
+        lists(rows.iter().map(|row| &row.categories)),
+        timestamps(results.iter().map(|result| result.published)),
+        timestamps(results.iter().map(|result| result.updated)),
+        strings(rows.iter().map(|row| row.doi.as_deref())),
+        strings(rows.iter().map(|row| row.journal_ref.as_deref())),
+        strings(rows.iter().map(|row| row.comment.as_deref())),
+        strings(rows.iter().map(|row| row.pdf_url.as_deref())),
+        strings(rows.iter().map(|row| Some(row.url.as_str()))),
+    ];
+
+    Ok(RecordBatch::try_new(table_schema(), columns)?)
+}
+
+/// Writes results to a Parquet file, one row group per [`ParquetWriter::write`]
+/// call. Calls with more rows than Parquet's maximum row group size (1024 *
+/// 1024 by default) are split further.
+pub struct ParquetWriter<W: Write + Send> {
+    writer: ArrowWriter<W>,
+}
+
+impl<W: Write + Send> ParquetWriter<W> {
+    pub fn new(writer: W) -> anyhow::Result<Self> {
+        Ok(Self {
+            writer: ArrowWriter::try_new(writer, table_schema(), None)?,
+        })
+    }
+
+    pub fn write(&mut self, results: &[ArxivResult]) -> anyhow::Result<()> {
+        self.writer.write(&to_record_batch(results)?)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Writes the footer and returns the underlying writer.
+    pub fn finish(self) -> anyhow::Result<W> {
+        Ok(self.writer.into_inner()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+    use arrow::array::{Array, ListArray};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    #[test]
+    fn test_record_batch() {
+        let batch = to_record_batch(&fixture()).unwrap();
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.num_columns(), crate::export::TABLE_COLUMNS.len());
+
+        let authors = batch
+            .column_by_name("authors")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap();
+        assert_eq!(authors.value(0).len(), 3);
+
+        let doi = batch.column_by_name("doi").unwrap();
+        assert!(doi.is_null(0));
+        assert!(!doi.is_null(1));
+    }
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("arxiv-api-rs-table-{}.parquet", std::process::id()));
+
+        let mut writer = ParquetWriter::new(std::fs::File::create(&path).unwrap()).unwrap();
+        writer.write(&fixture()).unwrap();
+        writer.write(&fixture()[..1]).unwrap();
+        writer.finish().unwrap();
+
+        let builder =
+            ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(builder.metadata().num_row_groups(), 2);
+        let reader = builder.build().unwrap();
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let n_rows = batches.iter().map(|batch| batch.num_rows()).sum::<usize>();
+        assert_eq!(n_rows, 4);
+        assert_eq!(batches[0].schema(), table_schema());
+    }
+}
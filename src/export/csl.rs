@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+
+use crate::export::{plain, split_name};
+use crate::models::ArxivResult;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CslName {
+    pub family: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub given: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CslDate {
+    #[serde(rename = "date-parts")]
+    pub date_parts: Vec<Vec<i32>>,
+}
+
+/// A CSL-JSON item, as read by Zotero, Mendeley and pandoc's citeproc.
+///
+/// Preprints are typed `article` with `arXiv` as publisher and the arXiv id
+/// as `number`; results with a journal reference become `article-journal`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CslItem {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub title: String,
+    pub author: Vec<CslName>,
+    pub issued: CslDate,
+    #[serde(rename = "abstract", skip_serializing_if = "Option::is_none")]
+    pub abstract_text: Option<String>,
+    #[serde(rename = "DOI", skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+    #[serde(rename = "URL")]
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<String>,
+    /// The arXiv categories, comma separated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyword: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl ArxivResult {
+    pub fn to_csl(&self) -> CslItem {
+        let arxiv_id = self.arxiv_id().map(|id| format!("arXiv:{}", id.base()));
+        let journal_ref = self.journal_ref_info();
+        let issued = match journal_ref
+            .as_ref()
+            .and_then(|journal_ref| journal_ref.year)
+        {
+            Some(year) => vec![year],
+            None => vec![
+                self.published.year(),
+                self.published.month() as i32,
+                self.published.day() as i32,
+            ],
+        };
+        let is_article = journal_ref
+            .as_ref()
+            .is_some_and(|journal_ref| journal_ref.journal.is_some());
+        let item_type = if is_article {
+            "article-journal"
+        } else {
+            "article"
+        };
+
+        CslItem {
+            id: arxiv_id.clone().unwrap_or_else(|| self.id.clone()),
+            item_type: item_type.to_string(),
+            title: plain(&self.title),
+            author: self
+                .authors
+                .iter()
+                .map(|author| {
+                    let name = split_name(author);
+                    CslName {
+                        family: name.family,
+                        given: name.given,
+                    }
+                })
+                .collect(),
+            issued: CslDate {
+                date_parts: vec![issued],
+            },
+            abstract_text: Some(plain(&self.summary)),
+            doi: self.doi.as_deref().map(|doi| doi.trim().to_string()),
+            url: self.id.clone(),
+            container_title: journal_ref
+                .as_ref()
+                .and_then(|journal_ref| journal_ref.journal.clone()),
+            volume: journal_ref
+                .as_ref()
+                .and_then(|journal_ref| journal_ref.volume.clone()),
+            issue: journal_ref
+                .as_ref()
+                .and_then(|journal_ref| journal_ref.issue.clone()),
+            page: journal_ref
+                .as_ref()
+                .and_then(|journal_ref| journal_ref.pages.clone()),
+            publisher: (!is_article).then(|| "arXiv".to_string()),
+            number: arxiv_id,
+            keyword: Some(
+                self.categories
+                    .iter()
+                    .map(|category| category.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            note: self.comment.as_deref().map(plain),
+        }
+    }
+}
+
+/// Serializes results as a CSL-JSON array.
+pub fn to_csl_json<'a>(
+    results: impl IntoIterator<Item = &'a ArxivResult>,
+) -> anyhow::Result<String> {
+    let items = results
+        .into_iter()
+        .map(ArxivResult::to_csl)
+        .collect::<Vec<_>>();
+
+    Ok(serde_json::to_string_pretty(&items)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_preprint() {
+        let item = fixture()[0].to_csl();
+        assert_eq!(item.id, "arXiv:2402.16893");
+        assert_eq!(item.item_type, "article");
+        assert_eq!(item.publisher.as_deref(), Some("arXiv"));
+        assert_eq!(item.issued.date_parts, vec![vec![2024, 2, 23]]);
+        assert_eq!(
+            item.author[0],
+            CslName {
+                family: "Zeng".to_string(),
+                given: "Shenglai".to_string()
+            }
+        );
+        assert_eq!(item.keyword.as_deref(), Some("cs.CR, cs.AI, cs.CL"));
+
+        let mut result = fixture()[1].clone();
+        result.journal_ref = Some("vol. 12, pp. 45-60, 2018".to_string());
+        let item = result.to_csl();
+        assert_eq!(item.item_type, "article");
+        assert_eq!(item.publisher.as_deref(), Some("arXiv"));
+        assert_eq!(item.container_title, None);
+    }
+
+    #[test]
+    fn test_csl_json() {
+        let results = fixture();
+        let json: serde_json::Value =
+            serde_json::from_str(&to_csl_json(&results).unwrap()).unwrap();
+
+        let article = &json[1];
+        assert_eq!(article["type"], "article-journal");
+        assert_eq!(article["container-title"], "Phys. Rev. Lett.");
+        assert_eq!(article["DOI"], "10.1103/PhysRevLett.72.957");
+        assert_eq!(article["issued"]["date-parts"][0][0], 1994);
+        assert_eq!(article["number"], "arXiv:hep-th/9901001");
+        assert!(article.get("publisher").is_none());
+    }
+}
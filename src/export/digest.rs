@@ -0,0 +1,337 @@
+use std::collections::BTreeMap;
+
+use quick_xml::escape::escape;
+use time::macros::format_description;
+
+use crate::export::plain;
+use crate::models::ArxivResult;
+
+const MARKDOWN_HEADING: &str = "## {{group}}\n\n";
+const MARKDOWN_ENTRY: &str =
+    "- **[{{title}}]({{url}})** ({{id}})  \n  *{{authors}}*  \n  {{abstract}}\n";
+const HTML_HEADING: &str = "<h2>{{group}}</h2>\n";
+const HTML_ENTRY: &str = "<li><a href=\"{{url}}\">{{title}}</a> ({{id}})<br><em>{{authors}}</em><p>{{abstract}}</p></li>\n";
+
+/// How a digest splits results into sections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DigestGrouping {
+    /// One section per primary category, in alphabetical order.
+    #[default]
+    PrimaryCategory,
+    /// One section per publication date, newest first.
+    Date,
+}
+
+/// Renders a list of results as a Markdown or HTML digest, e.g. for a weekly
+/// mail or chat post.
+///
+/// Entries and section headings can be customized with templates containing
+/// `{{placeholder}}`s. Entry templates know `title`, `authors`, `abstract`,
+/// `id`, `url`, `pdf_url`, `published`, `primary_category` and `categories`;
+/// heading templates know `group` and `count`. Values are escaped for the
+/// output format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Digest {
+    pub title: String,
+    pub grouping: DigestGrouping,
+    /// Abstracts longer than this many characters are cut at a word boundary.
+    pub abstract_length: usize,
+    pub markdown_heading: String,
+    pub markdown_entry: String,
+    pub html_heading: String,
+    pub html_entry: String,
+}
+
+impl Digest {
+    pub fn new<S: ToString>(title: S) -> Self {
+        Self {
+            title: title.to_string(),
+            grouping: DigestGrouping::default(),
+            abstract_length: 300,
+            markdown_heading: MARKDOWN_HEADING.to_string(),
+            markdown_entry: MARKDOWN_ENTRY.to_string(),
+            html_heading: HTML_HEADING.to_string(),
+            html_entry: HTML_ENTRY.to_string(),
+        }
+    }
+
+    pub fn with_grouping(mut self, grouping: DigestGrouping) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    pub fn with_abstract_length(mut self, abstract_length: usize) -> Self {
+        self.abstract_length = abstract_length;
+        self
+    }
+
+    pub fn with_markdown_templates<H: ToString, E: ToString>(
+        mut self,
+        heading: H,
+        entry: E,
+    ) -> Self {
+        self.markdown_heading = heading.to_string();
+        self.markdown_entry = entry.to_string();
+        self
+    }
+
+    pub fn with_html_templates<H: ToString, E: ToString>(mut self, heading: H, entry: E) -> Self {
+        self.html_heading = heading.to_string();
+        self.html_entry = entry.to_string();
+        self
+    }
+
+    fn groups<'a>(&self, results: &'a [ArxivResult]) -> Vec<(String, Vec<&'a ArxivResult>)> {
+        let mut groups: BTreeMap<String, Vec<&ArxivResult>> = BTreeMap::new();
+        for result in results {
+            let key = match self.grouping {
+                DigestGrouping::PrimaryCategory => result.primary_category.as_str().to_string(),
+                DigestGrouping::Date => result
+                    .published
+                    .format(format_description!("[year]-[month]-[day]"))
+                    .unwrap_or_default(),
+            };
+            groups.entry(key).or_default().push(result);
+        }
+
+        let mut groups = groups.into_iter().collect::<Vec<_>>();
+        if self.grouping == DigestGrouping::Date {
+            groups.reverse();
+        }
+        groups
+    }
+
+    fn heading(&self, key: &str, results: &[&ArxivResult]) -> String {
+        match self.grouping {
+            DigestGrouping::PrimaryCategory => match results[0].primary_category.name() {
+                Some(name) => format!("{} ({})", name, key),
+                None => key.to_string(),
+            },
+            DigestGrouping::Date => key.to_string(),
+        }
+    }
+
+    fn entry_value(&self, result: &ArxivResult, name: &str) -> Option<String> {
+        let value = match name {
+            "title" => plain(&result.title),
+            "authors" => result.authors.join(", "),
+            "abstract" => truncate(&plain(&result.summary), self.abstract_length),
+            "id" => result
+                .arxiv_id()
+                .map_or_else(|| result.id.clone(), |id| id.to_string()),
+            "url" => result.id.clone(),
+            "pdf_url" => result.pdf_url.clone().unwrap_or_default(),
+            "published" => result
+                .published
+                .format(format_description!("[year]-[month]-[day]"))
+                .ok()?,
+            "primary_category" => result.primary_category.as_str().to_string(),
+            "categories" => result
+                .categories
+                .iter()
+                .map(|category| category.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn render(
+        &self,
+        results: &[ArxivResult],
+        heading_template: &str,
+        entry_template: &str,
+        escape_value: fn(&str) -> String,
+    ) -> anyhow::Result<Vec<(String, Vec<String>)>> {
+        self.groups(results)
+            .into_iter()
+            .map(|(key, group)| {
+                let count = group.len().to_string();
+                let heading = self.heading(&key, &group);
+                let heading = render_template(heading_template, |name| match name {
+                    "group" => Some(escape_value(&heading)),
+                    "count" => Some(count.clone()),
+                    _ => None,
+                })?;
+                let entries = group
+                    .iter()
+                    .map(|result| {
+                        render_template(entry_template, |name| {
+                            self.entry_value(result, name)
+                                .map(|value| escape_value(&value))
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                Ok((heading, entries))
+            })
+            .collect()
+    }
+
+    pub fn to_markdown(&self, results: &[ArxivResult]) -> anyhow::Result<String> {
+        let mut markdown = format!("# {}\n\n", escape_markdown(&self.title));
+        for (heading, entries) in self.render(
+            results,
+            &self.markdown_heading,
+            &self.markdown_entry,
+            escape_markdown,
+        )? {
+            markdown.push_str(&heading);
+            for entry in entries {
+                markdown.push_str(&entry);
+            }
+            markdown.push('\n');
+        }
+
+        Ok(markdown)
+    }
+
+    /// Renders an HTML fragment, without `<html>` and `<body>`.
+    pub fn to_html(&self, results: &[ArxivResult]) -> anyhow::Result<String> {
+        let mut html = format!("<h1>{}</h1>\n", escape(&self.title));
+        for (heading, entries) in
+            self.render(results, &self.html_heading, &self.html_entry, |value| {
+                escape(value).into_owned()
+            })?
+        {
+            html.push_str(&heading);
+            html.push_str("<ul>\n");
+            for entry in entries {
+                html.push_str(&entry);
+            }
+            html.push_str("</ul>\n");
+        }
+
+        Ok(html)
+    }
+}
+
+/// Cuts `text` to at most `length` characters at a word boundary, marking
+/// the cut with an ellipsis. If not even the first word fits, it is cut
+/// mid-word instead.
+fn truncate(text: &str, length: usize) -> String {
+    if text.chars().count() <= length {
+        return text.to_string();
+    }
+
+    let mut truncated = String::new();
+    for word in text.split(' ') {
+        let separator = usize::from(!truncated.is_empty());
+        if truncated.chars().count() + separator + word.chars().count() > length {
+            break;
+        }
+        if separator == 1 {
+            truncated.push(' ');
+        }
+        truncated.push_str(word);
+    }
+    if truncated.is_empty() {
+        truncated = text.chars().take(length).collect();
+    }
+    truncated.push('…');
+    truncated
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn render_template(
+    template: &str,
+    value: impl Fn(&str) -> Option<String>,
+) -> anyhow::Result<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find("}}") else {
+            anyhow::bail!("Unclosed placeholder in digest template: {}", template);
+        };
+        let name = rest[start + 2..start + end].trim();
+        match value(name) {
+            Some(value) => rendered.push_str(&value),
+            None => anyhow::bail!("Unknown placeholder {{{{{}}}}} in digest template", name),
+        }
+        rest = &rest[start + end + 2..];
+    }
+    rendered.push_str(rest);
+
+    Ok(rendered)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short text", 20), "short text");
+        assert_eq!(truncate("one two three four", 10), "one two…");
+        assert_eq!(truncate("supercalifragilistic words", 5), "super…");
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = Digest::new("Weekly papers")
+            .with_abstract_length(40)
+            .to_markdown(&fixture())
+            .unwrap();
+
+        assert!(markdown.starts_with("# Weekly papers\n\n## "));
+        let sections = markdown
+            .lines()
+            .filter(|line| line.starts_with("## "))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sections,
+            vec![
+                "## Cryptography and Security (cs.CR)",
+                "## Machine Learning (cs.LG)",
+                "## High Energy Physics - Theory (hep-th)",
+            ]
+        );
+        assert!(markdown.contains("(http://arxiv.org/abs/2402.16893v1)** (2402.16893v1)"));
+        assert!(markdown.contains("*Shenglai Zeng, Jiankun Zhang, Pengfei He*"));
+        assert!(markdown.contains("…\n"));
+    }
+
+    #[test]
+    fn test_html_by_date() {
+        let html = Digest::new("Papers & more")
+            .with_grouping(DigestGrouping::Date)
+            .with_html_templates(
+                "<h3>{{ group }} ({{count}})</h3>\n",
+                "<li>{{published}}: {{title}}</li>\n",
+            )
+            .to_html(&fixture())
+            .unwrap();
+
+        assert!(html.starts_with("<h1>Papers &amp; more</h1>\n<h3>2024-02-23 (1)</h3>\n<ul>\n"));
+        let dates = html
+            .lines()
+            .filter(|line| line.starts_with("<h3>"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dates,
+            vec![
+                "<h3>2024-02-23 (1)</h3>",
+                "<h3>2021-01-01 (1)</h3>",
+                "<h3>1999-01-04 (1)</h3>",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_placeholder() {
+        let digest = Digest::new("Digest").with_markdown_templates("## {{group}}\n", "{{doi}}\n");
+        assert!(digest.to_markdown(&fixture()).is_err());
+    }
+}
@@ -0,0 +1,357 @@
+use quick_xml::escape::escape;
+use serde::Serialize;
+use time::format_description::well_known::{Rfc2822, Rfc3339};
+use time::OffsetDateTime;
+
+use crate::export::plain;
+use crate::models::ArxivResult;
+
+const ARXIV_SCHEMA: &str = "http://arxiv.org/schemas/atom";
+
+/// Channel-level information of a published reading list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedMetadata {
+    pub title: String,
+    /// The web page the feed belongs to.
+    pub link: String,
+    /// Where the feed itself is published.
+    pub feed_url: Option<String>,
+    pub description: Option<String>,
+    /// Defaults to the latest `updated` of the results.
+    pub updated: Option<OffsetDateTime>,
+}
+
+impl FeedMetadata {
+    pub fn new<S: ToString, L: ToString>(title: S, link: L) -> Self {
+        Self {
+            title: title.to_string(),
+            link: link.to_string(),
+            feed_url: None,
+            description: None,
+            updated: None,
+        }
+    }
+
+    pub fn with_feed_url<S: ToString>(mut self, feed_url: S) -> Self {
+        self.feed_url = Some(feed_url.to_string());
+        self
+    }
+
+    pub fn with_description<S: ToString>(mut self, description: S) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    pub fn with_updated(mut self, updated: OffsetDateTime) -> Self {
+        self.updated = Some(updated);
+        self
+    }
+
+    fn updated(&self, results: &[ArxivResult]) -> OffsetDateTime {
+        self.updated
+            .or_else(|| results.iter().map(|result| result.updated).max())
+            .unwrap_or_else(OffsetDateTime::now_utc)
+    }
+}
+
+fn text_element(xml: &mut String, indent: &str, name: &str, text: &str) {
+    xml.push_str(&format!(
+        "{}<{}>{}</{}>\n",
+        indent,
+        name,
+        escape(text),
+        name
+    ));
+}
+
+/// Renders an Atom 1.0 feed using arXiv's own extension elements, so that
+/// [`crate::parse_feed`] reads the results back unchanged.
+pub fn to_atom_feed(metadata: &FeedMetadata, results: &[ArxivResult]) -> anyhow::Result<String> {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<feed xmlns=\"http://www.w3.org/2005/Atom\" xmlns:arxiv=\"{}\">\n",
+        ARXIV_SCHEMA
+    ));
+
+    text_element(&mut xml, "  ", "title", &metadata.title);
+    text_element(
+        &mut xml,
+        "  ",
+        "id",
+        metadata.feed_url.as_deref().unwrap_or(&metadata.link),
+    );
+    if let Some(feed_url) = &metadata.feed_url {
+        xml.push_str(&format!(
+            "  <link href=\"{}\" rel=\"self\" type=\"application/atom+xml\"/>\n",
+            escape(feed_url)
+        ));
+    }
+    xml.push_str(&format!(
+        "  <link href=\"{}\" rel=\"alternate\" type=\"text/html\"/>\n",
+        escape(&metadata.link)
+    ));
+    text_element(
+        &mut xml,
+        "  ",
+        "updated",
+        &metadata.updated(results).format(&Rfc3339)?,
+    );
+    if let Some(description) = &metadata.description {
+        text_element(&mut xml, "  ", "subtitle", description);
+    }
+
+    for result in results {
+        xml.push_str("  <entry>\n");
+        text_element(&mut xml, "    ", "id", &result.id);
+        text_element(
+            &mut xml,
+            "    ",
+            "updated",
+            &result.updated.format(&Rfc3339)?,
+        );
+        text_element(
+            &mut xml,
+            "    ",
+            "published",
+            &result.published.format(&Rfc3339)?,
+        );
+        text_element(&mut xml, "    ", "title", &result.title);
+        text_element(&mut xml, "    ", "summary", &result.summary);
+        for author in result.authors_detailed() {
+            xml.push_str("    <author>\n");
+            text_element(&mut xml, "      ", "name", &author.name);
+            for affiliation in &author.affiliations {
+                text_element(&mut xml, "      ", "arxiv:affiliation", affiliation);
+            }
+            xml.push_str("    </author>\n");
+        }
+        if let Some(doi) = &result.doi {
+            text_element(&mut xml, "    ", "arxiv:doi", doi);
+        }
+        if let Some(comment) = &result.comment {
+            text_element(&mut xml, "    ", "arxiv:comment", comment);
+        }
+        if let Some(journal_ref) = &result.journal_ref {
+            text_element(&mut xml, "    ", "arxiv:journal_ref", journal_ref);
+        }
+        for link in &result.links {
+            xml.push_str("    <link");
+            if let Some(title) = &link.title {
+                xml.push_str(&format!(" title=\"{}\"", escape(title)));
+            }
+            xml.push_str(&format!(
+                " href=\"{}\" rel=\"{}\"",
+                escape(&link.href),
+                escape(&link.rel)
+            ));
+            if let Some(content_type) = &link.content_type {
+                xml.push_str(&format!(" type=\"{}\"", escape(content_type)));
+            }
+            xml.push_str("/>\n");
+        }
+        xml.push_str(&format!(
+            "    <arxiv:primary_category term=\"{}\" scheme=\"{}\"/>\n",
+            escape(result.primary_category.as_str()),
+            ARXIV_SCHEMA
+        ));
+        for category in &result.categories {
+            xml.push_str(&format!(
+                "    <category term=\"{}\" scheme=\"{}\"/>\n",
+                escape(category.as_str()),
+                ARXIV_SCHEMA
+            ));
+        }
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+
+    Ok(xml)
+}
+
+/// Renders an RSS 2.0 channel; authors go into `dc:creator`, since RSS's own
+/// `author` element expects an e-mail address.
+pub fn to_rss_feed(metadata: &FeedMetadata, results: &[ArxivResult]) -> anyhow::Result<String> {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str("  <channel>\n");
+
+    text_element(&mut xml, "    ", "title", &metadata.title);
+    text_element(&mut xml, "    ", "link", &metadata.link);
+    text_element(
+        &mut xml,
+        "    ",
+        "description",
+        metadata.description.as_deref().unwrap_or(&metadata.title),
+    );
+    if let Some(feed_url) = &metadata.feed_url {
+        xml.push_str(&format!(
+            "    <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+            escape(feed_url)
+        ));
+    }
+    text_element(
+        &mut xml,
+        "    ",
+        "lastBuildDate",
+        &metadata.updated(results).format(&Rfc2822)?,
+    );
+
+    for result in results {
+        xml.push_str("    <item>\n");
+        text_element(&mut xml, "      ", "title", &plain(&result.title));
+        text_element(&mut xml, "      ", "link", &result.id);
+        text_element(&mut xml, "      ", "description", &plain(&result.summary));
+        for author in &result.authors {
+            text_element(&mut xml, "      ", "dc:creator", author);
+        }
+        for category in &result.categories {
+            text_element(&mut xml, "      ", "category", category.as_str());
+        }
+        xml.push_str(&format!(
+            "      <guid isPermaLink=\"true\">{}</guid>\n",
+            escape(&result.id)
+        ));
+        text_element(
+            &mut xml,
+            "      ",
+            "pubDate",
+            &result.published.format(&Rfc2822)?,
+        );
+        xml.push_str("    </item>\n");
+    }
+    xml.push_str("  </channel>\n</rss>\n");
+
+    Ok(xml)
+}
+
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    feed_url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedAuthor<'a> {
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+struct JsonFeedItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_url: Option<String>,
+    title: String,
+    content_text: String,
+    date_published: String,
+    date_modified: String,
+    authors: Vec<JsonFeedAuthor<'a>>,
+    tags: Vec<&'a str>,
+}
+
+/// Renders a JSON Feed 1.1 document. The DOI, if any, becomes the item's
+/// `external_url`.
+pub fn to_json_feed(metadata: &FeedMetadata, results: &[ArxivResult]) -> anyhow::Result<String> {
+    let items = results
+        .iter()
+        .map(|result| {
+            Ok(JsonFeedItem {
+                id: &result.id,
+                url: &result.id,
+                external_url: result
+                    .doi
+                    .as_ref()
+                    .map(|doi| format!("https://doi.org/{}", doi.trim())),
+                title: plain(&result.title),
+                content_text: plain(&result.summary),
+                date_published: result.published.format(&Rfc3339)?,
+                date_modified: result.updated.format(&Rfc3339)?,
+                authors: result
+                    .authors
+                    .iter()
+                    .map(|name| JsonFeedAuthor { name })
+                    .collect(),
+                tags: result
+                    .categories
+                    .iter()
+                    .map(|category| category.as_str())
+                    .collect(),
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let feed = JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: &metadata.title,
+        home_page_url: &metadata.link,
+        feed_url: metadata.feed_url.as_deref(),
+        description: metadata.description.as_deref(),
+        items,
+    };
+
+    Ok(serde_json::to_string_pretty(&feed)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+    use crate::parse::{parse_feed_reader, parse_feed_str, ParseMode};
+
+    fn metadata() -> FeedMetadata {
+        FeedMetadata::new("Reading list <week 8>", "https://example.org/reading")
+            .with_feed_url("https://example.org/reading.xml")
+            .with_description("Papers & preprints")
+    }
+
+    #[test]
+    fn test_atom_round_trip() {
+        let results = fixture();
+        let atom = to_atom_feed(&metadata(), &results).unwrap();
+        assert!(atom.contains("<title>Reading list &lt;week 8&gt;</title>"));
+        assert!(atom.contains("<updated>2024-02-23T18:35:15Z</updated>"));
+
+        let parsed = parse_feed_str(&atom, ParseMode::Strict).unwrap();
+        assert_eq!(format!("{:?}", parsed.results), format!("{:?}", results));
+
+        let streamed = parse_feed_reader(atom.as_bytes()).unwrap();
+        assert_eq!(format!("{:?}", streamed.results), format!("{:?}", results));
+    }
+
+    #[test]
+    fn test_rss() {
+        let rss = to_rss_feed(&metadata(), &fixture()).unwrap();
+        assert!(rss.contains("<description>Papers &amp; preprints</description>"));
+        assert!(rss.contains("<lastBuildDate>Fri, 23 Feb 2024 18:35:15 +0000</lastBuildDate>"));
+        assert!(rss.contains("<dc:creator>Shenglai Zeng</dc:creator>"));
+        assert!(
+            rss.contains("<guid isPermaLink=\"true\">http://arxiv.org/abs/hep-th/9901001v2</guid>")
+        );
+        assert_eq!(rss.matches("<item>").count(), 3);
+    }
+
+    #[test]
+    fn test_json_feed() {
+        let json: serde_json::Value =
+            serde_json::from_str(&to_json_feed(&metadata(), &fixture()).unwrap()).unwrap();
+
+        assert_eq!(json["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(json["items"].as_array().unwrap().len(), 3);
+
+        let item = &json["items"][1];
+        assert_eq!(item["id"], "http://arxiv.org/abs/hep-th/9901001v2");
+        assert_eq!(
+            item["external_url"],
+            "https://doi.org/10.1103/PhysRevLett.72.957"
+        );
+        assert_eq!(item["date_published"], "1999-01-04T17:02:43Z");
+        assert_eq!(item["tags"][1], "gr-qc");
+        assert!(json["items"][0].get("external_url").is_none());
+    }
+}
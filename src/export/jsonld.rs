@@ -0,0 +1,170 @@
+use serde_json::{json, Value};
+use time::format_description::well_known::Rfc3339;
+
+use crate::export::{plain, split_name};
+use crate::models::ArxivResult;
+
+const CATEGORY_TAXONOMY: &str = "https://arxiv.org/category_taxonomy";
+
+fn identifier(property: &str, value: &str) -> Value {
+    json!({
+        "@type": "PropertyValue",
+        "propertyID": property,
+        "value": value,
+    })
+}
+
+impl ArxivResult {
+    /// The paper as a schema.org `ScholarlyArticle`, without `@context`.
+    fn scholarly_article(&self) -> anyhow::Result<Value> {
+        let authors = self
+            .authors_detailed()
+            .into_iter()
+            .map(|author| {
+                let name = split_name(&author.name);
+                let mut person = json!({
+                    "@type": "Person",
+                    "name": author.name,
+                    "familyName": name.family,
+                });
+                if !name.given.is_empty() {
+                    person["givenName"] = json!(name.given);
+                }
+                if !author.affiliations.is_empty() {
+                    person["affiliation"] = author
+                        .affiliations
+                        .iter()
+                        .map(|affiliation| json!({"@type": "Organization", "name": affiliation}))
+                        .collect();
+                }
+                person
+            })
+            .collect::<Vec<_>>();
+
+        let mut identifiers = vec![];
+        if let Some(id) = self.arxiv_id() {
+            identifiers.push(identifier("arXiv", &id.to_string()));
+        }
+        if let Some(doi) = &self.doi {
+            identifiers.push(identifier("DOI", doi.trim()));
+        }
+
+        let about = self
+            .categories
+            .iter()
+            .map(|category| {
+                let mut term = json!({
+                    "@type": "DefinedTerm",
+                    "termCode": category.as_str(),
+                    "inDefinedTermSet": CATEGORY_TAXONOMY,
+                });
+                if let Some(name) = category.name() {
+                    term["name"] = json!(name);
+                }
+                term
+            })
+            .collect::<Vec<_>>();
+
+        let mut article = json!({
+            "@type": "ScholarlyArticle",
+            "@id": self.id,
+            "url": self.id,
+            "headline": plain(&self.title),
+            "name": plain(&self.title),
+            "abstract": plain(&self.summary),
+            "author": authors,
+            "identifier": identifiers,
+            "datePublished": self.published.format(&Rfc3339)?,
+            "dateModified": self.updated.format(&Rfc3339)?,
+            "about": about,
+            "publisher": {"@type": "Organization", "name": "arXiv"},
+        });
+        if let Some(doi) = &self.doi {
+            article["sameAs"] = json!(format!("https://doi.org/{}", doi.trim()));
+        }
+        if let Some(pdf_url) = &self.pdf_url {
+            article["encoding"] = json!({
+                "@type": "MediaObject",
+                "contentUrl": pdf_url,
+                "encodingFormat": "application/pdf",
+            });
+        }
+        if let Some(journal) = self
+            .journal_ref_info()
+            .and_then(|journal_ref| journal_ref.journal)
+        {
+            article["isPartOf"] = json!({"@type": "Periodical", "name": journal});
+        }
+
+        Ok(article)
+    }
+
+    /// A schema.org `ScholarlyArticle` JSON-LD document, e.g. for a
+    /// `<script type="application/ld+json">` tag.
+    pub fn to_json_ld(&self) -> anyhow::Result<Value> {
+        let mut article = self.scholarly_article()?;
+        article["@context"] = json!("https://schema.org");
+
+        Ok(article)
+    }
+}
+
+/// Several results as one JSON-LD document with an `@graph`.
+pub fn to_json_ld_graph<'a>(
+    results: impl IntoIterator<Item = &'a ArxivResult>,
+) -> anyhow::Result<Value> {
+    let graph = results
+        .into_iter()
+        .map(ArxivResult::scholarly_article)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(json!({
+        "@context": "https://schema.org",
+        "@graph": graph,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_to_json_ld() {
+        let article = fixture()[1].to_json_ld().unwrap();
+
+        assert_eq!(article["@context"], "https://schema.org");
+        assert_eq!(article["@type"], "ScholarlyArticle");
+        assert_eq!(
+            article["headline"],
+            "Black Hole Entropy and the Dimensional Continuation of the Gauss-Bonnet Theorem"
+        );
+        assert_eq!(article["datePublished"], "1999-01-04T17:02:43Z");
+
+        let author = &article["author"][1];
+        assert_eq!(author["familyName"], "Teitelboim");
+        assert_eq!(author["givenName"], "Claudio");
+        assert_eq!(
+            author["affiliation"][1]["name"],
+            "Institute for Advanced Study"
+        );
+        assert!(article["author"][2].get("affiliation").is_none());
+
+        assert_eq!(article["identifier"][0]["value"], "hep-th/9901001v2");
+        assert_eq!(article["identifier"][1]["propertyID"], "DOI");
+        assert_eq!(
+            article["sameAs"],
+            "https://doi.org/10.1103/PhysRevLett.72.957"
+        );
+        assert_eq!(article["about"][0]["termCode"], "hep-th");
+        assert_eq!(article["about"][0]["name"], "High Energy Physics - Theory");
+        assert_eq!(article["isPartOf"]["name"], "Phys. Rev. Lett.");
+    }
+
+    #[test]
+    fn test_graph() {
+        let graph = to_json_ld_graph(&fixture()).unwrap();
+        assert_eq!(graph["@graph"].as_array().unwrap().len(), 3);
+        assert!(graph["@graph"][0].get("@context").is_none());
+    }
+}
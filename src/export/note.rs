@@ -0,0 +1,355 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use time::format_description::well_known::Rfc3339;
+
+use crate::export::plain;
+use crate::models::ArxivResult;
+
+/// Separates the generated part of a note from the user's own notes.
+pub const DEFAULT_NOTE_MARKER: &str = "<!-- notes -->";
+
+const FRONTMATTER_DELIMITER: &str = "---";
+
+/// A double-quoted YAML scalar, safe for any text.
+fn yaml_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn yaml_list<'a>(values: impl IntoIterator<Item = &'a str>) -> String {
+    let items = values
+        .into_iter()
+        .map(|value| format!("\n  - {}", yaml_string(value)))
+        .collect::<String>();
+    if items.is_empty() {
+        " []".to_string()
+    } else {
+        items
+    }
+}
+
+/// Top-level frontmatter entries as `(key, entry)`, where an entry is the key
+/// line together with any indented continuation lines.
+fn frontmatter_entries(frontmatter: &str) -> Vec<(String, String)> {
+    let mut entries: Vec<(String, String)> = vec![];
+    for line in frontmatter.lines() {
+        let is_continuation = line.starts_with([' ', '\t', '-']) || line.trim().is_empty();
+        match entries.last_mut() {
+            Some((_, entry)) if is_continuation => {
+                entry.push('\n');
+                entry.push_str(line);
+            }
+            _ => {
+                let key = line.split_once(':').map_or(line, |(key, _)| key).trim();
+                entries.push((key.to_string(), line.to_string()));
+            }
+        }
+    }
+    entries
+}
+
+/// Splits a note into its frontmatter (without delimiters) and the rest.
+/// Lines may end in `\n` or `\r\n`.
+fn split_frontmatter(note: &str) -> Option<(&str, &str)> {
+    let rest = note.strip_prefix(FRONTMATTER_DELIMITER)?;
+    let rest = rest
+        .strip_prefix('\n')
+        .or_else(|| rest.strip_prefix("\r\n"))?;
+
+    let mut end = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end_matches(['\r', '\n']) == FRONTMATTER_DELIMITER {
+            return Some((&rest[..end], &rest[end + line.len()..]));
+        }
+        end += line.len();
+    }
+    None
+}
+
+impl ArxivResult {
+    fn note_entries(&self) -> anyhow::Result<Vec<(&'static str, String)>> {
+        let id = self.arxiv_id();
+        let mut entries = vec![(
+            "id",
+            yaml_string(
+                &id.as_ref()
+                    .map_or_else(|| self.id.clone(), |id| id.base().to_string()),
+            ),
+        )];
+        if let Some(version) = id.as_ref().and_then(|id| id.version()) {
+            entries.push(("version", version.to_string()));
+        }
+        entries.push(("title", yaml_string(&plain(&self.title))));
+        entries.push((
+            "authors",
+            yaml_list(self.authors.iter().map(String::as_str)),
+        ));
+        entries.push((
+            "primary_category",
+            yaml_string(self.primary_category.as_str()),
+        ));
+        entries.push((
+            "categories",
+            yaml_list(self.categories.iter().map(|category| category.as_str())),
+        ));
+        entries.push(("published", self.published.format(&Rfc3339)?));
+        entries.push(("updated", self.updated.format(&Rfc3339)?));
+        if let Some(doi) = &self.doi {
+            entries.push(("doi", yaml_string(doi.trim())));
+        }
+        if let Some(journal_ref) = &self.journal_ref {
+            entries.push(("journal_ref", yaml_string(&plain(journal_ref))));
+        }
+        entries.push(("url", yaml_string(&self.id)));
+        if let Some(pdf_url) = &self.pdf_url {
+            entries.push(("pdf", yaml_string(pdf_url)));
+        }
+
+        Ok(entries)
+    }
+
+    /// The YAML frontmatter of [`ArxivResult::to_note`], delimiters included.
+    pub fn to_note_frontmatter(&self) -> anyhow::Result<String> {
+        let mut frontmatter = format!("{}\n", FRONTMATTER_DELIMITER);
+        for (key, value) in self.note_entries()? {
+            frontmatter.push_str(&format!("{}:{}\n", key, yaml_value(&value)));
+        }
+        frontmatter.push_str(FRONTMATTER_DELIMITER);
+        frontmatter.push('\n');
+
+        Ok(frontmatter)
+    }
+
+    /// A Markdown note with YAML frontmatter, the title as heading and the
+    /// abstract as body, followed by `marker` and room for the reader's notes.
+    pub fn to_note(&self, marker: &str) -> anyhow::Result<String> {
+        Ok(format!(
+            "{}\n{}{}\n\n",
+            self.to_note_frontmatter()?,
+            self.note_body(),
+            marker
+        ))
+    }
+
+    fn note_body(&self) -> String {
+        format!("# {}\n\n{}\n\n", plain(&self.title), plain(&self.summary))
+    }
+
+    /// Refreshes an existing note: keys of the generated frontmatter are
+    /// replaced in place, keys the user added are kept, and the generated body
+    /// is rewritten. Everything after `marker` stays untouched; without a
+    /// marker the whole body is kept. The generated part follows the line
+    /// endings of the note's first line.
+    pub fn update_note(&self, note: &str, marker: &str) -> anyhow::Result<String> {
+        let (frontmatter, body) = split_frontmatter(note).unwrap_or(("", note));
+        let is_crlf = note
+            .find('\n')
+            .is_some_and(|end| note[..end].ends_with('\r'));
+
+        let mut generated = self.note_entries()?;
+        let mut updated = format!("{}\n", FRONTMATTER_DELIMITER);
+        for (key, entry) in frontmatter_entries(frontmatter) {
+            match generated.iter().position(|(name, _)| *name == key) {
+                Some(i) => {
+                    let (name, value) = generated.remove(i);
+                    updated.push_str(&format!("{}:{}\n", name, yaml_value(&value)));
+                }
+                None => {
+                    updated.push_str(&entry);
+                    updated.push('\n');
+                }
+            }
+        }
+        for (name, value) in generated {
+            updated.push_str(&format!("{}:{}\n", name, yaml_value(&value)));
+        }
+        updated.push_str(FRONTMATTER_DELIMITER);
+        updated.push('\n');
+
+        let kept = match body.find(marker) {
+            Some(start) => {
+                updated.push('\n');
+                updated.push_str(&self.note_body());
+                &body[start..]
+            }
+            None => body,
+        };
+        if is_crlf {
+            updated = updated.replace('\n', "\r\n");
+        }
+        updated.push_str(kept);
+
+        Ok(updated)
+    }
+}
+
+/// Block lists start on the next line; scalars follow the colon after a space.
+fn yaml_value(value: &str) -> String {
+    if value.starts_with('\n') || value.starts_with(' ') {
+        value.to_string()
+    } else {
+        format!(" {}", value)
+    }
+}
+
+/// A directory of notes, one Markdown file per paper, named after its arXiv
+/// id (`2402.16893.md`, `hep-th_9901001.md`).
+#[derive(Debug, Clone)]
+pub struct NoteVault {
+    dir: PathBuf,
+    marker: String,
+}
+
+impl NoteVault {
+    pub fn new(dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+
+        Ok(Self {
+            dir,
+            marker: DEFAULT_NOTE_MARKER.to_string(),
+        })
+    }
+
+    pub fn with_marker<S: ToString>(mut self, marker: S) -> Self {
+        self.marker = marker.to_string();
+        self
+    }
+
+    pub fn path(&self, result: &ArxivResult) -> PathBuf {
+        let name = result
+            .arxiv_id()
+            .map_or_else(|| result.id.clone(), |id| id.base().to_string());
+        let name = name.replace(['/', ':', '\\'], "_");
+        self.dir.join(format!("{}.md", name))
+    }
+
+    /// Creates the note for `result`, or updates it if it already exists.
+    pub fn write(&self, result: &ArxivResult) -> anyhow::Result<PathBuf> {
+        let path = self.path(result);
+        let note = if path.exists() {
+            let note = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            result.update_note(&note, &self.marker)?
+        } else {
+            result.to_note(&self.marker)?
+        };
+        fs::write(&path, note).with_context(|| format!("Failed to write {}", path.display()))?;
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_to_note() {
+        let note = fixture()[1].to_note(DEFAULT_NOTE_MARKER).unwrap();
+        assert!(note.starts_with("---\nid: \"hep-th/9901001\"\nversion: 2\ntitle: \"Black Hole"));
+        assert!(note.contains("authors:\n  - \"Maximo Banados\"\n"));
+        assert!(note.contains("categories:\n  - \"hep-th\"\n  - \"gr-qc\"\n"));
+        assert!(note.contains("published: 1999-01-04T17:02:43Z\n"));
+        assert!(note.contains("doi: \"10.1103/PhysRevLett.72.957\"\n"));
+        assert!(note.contains("---\n\n# Black Hole Entropy"));
+        assert!(note.ends_with("<!-- notes -->\n\n"));
+    }
+
+    #[test]
+    fn test_yaml_string() {
+        assert_eq!(yaml_string("a \"b\" \\c"), "\"a \\\"b\\\" \\\\c\"");
+        assert_eq!(
+            yaml_string("key: value # no comment"),
+            "\"key: value # no comment\""
+        );
+    }
+
+    #[test]
+    fn test_update_note() {
+        let results = fixture();
+        let note = results[0].to_note(DEFAULT_NOTE_MARKER).unwrap();
+        let note = note.replacen("---\n", "---\ntags:\n  - to-read\n", 1)
+            + "Good baseline for our RAG eval.\n";
+
+        let mut result = results[0].clone();
+        result.title = "A revised title".to_string();
+        let updated = result.update_note(&note, DEFAULT_NOTE_MARKER).unwrap();
+
+        assert!(updated.starts_with("---\ntags:\n  - to-read\nid: \"2402.16893\"\n"));
+        assert!(updated.contains("title: \"A revised title\"\n"));
+        assert!(updated.contains("\n# A revised title\n"));
+        assert!(updated.ends_with("<!-- notes -->\n\nGood baseline for our RAG eval.\n"));
+        assert_eq!(updated.matches("---\n").count(), 2);
+        assert_eq!(
+            result.update_note(&updated, DEFAULT_NOTE_MARKER).unwrap(),
+            updated
+        );
+    }
+
+    #[test]
+    fn test_update_note_crlf() {
+        let result = &fixture()[0];
+        let note = result
+            .to_note(DEFAULT_NOTE_MARKER)
+            .unwrap()
+            .replacen("---\n", "---\ntags:\n  - to-read\n", 1)
+            .replace('\n', "\r\n");
+
+        let updated = result.update_note(&note, DEFAULT_NOTE_MARKER).unwrap();
+        assert!(updated.starts_with("---\r\ntags:\r\n  - to-read\r\nid: \"2402.16893\"\r\n"));
+        assert_eq!(updated.matches("---").count(), 2);
+        assert!(updated.ends_with("<!-- notes -->\r\n\r\n"));
+        assert_eq!(
+            updated.matches('\n').count(),
+            updated.matches("\r\n").count()
+        );
+        assert_eq!(updated, note);
+    }
+
+    #[test]
+    fn test_update_note_without_marker() {
+        let result = &fixture()[2];
+        let updated = result
+            .update_note("My own summary.\n", DEFAULT_NOTE_MARKER)
+            .unwrap();
+
+        assert!(updated.starts_with("---\nid: \"2101.00027\"\n"));
+        assert!(updated.ends_with("---\nMy own summary.\n"));
+    }
+
+    #[test]
+    fn test_note_vault() {
+        let dir = std::env::temp_dir().join(format!("arxiv-api-rs-notes-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let vault = NoteVault::new(&dir).unwrap();
+        let results = fixture();
+        let path = vault.write(&results[1]).unwrap();
+        assert_eq!(path, dir.join("hep-th_9901001.md"));
+
+        let note = fs::read_to_string(&path).unwrap() + "Read section 3 again.\n";
+        fs::write(&path, note).unwrap();
+        vault.write(&results[1]).unwrap();
+        let note = fs::read_to_string(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(note.ends_with("<!-- notes -->\n\nRead section 3 again.\n"));
+        assert_eq!(note.matches("# Black Hole Entropy").count(), 1);
+    }
+}
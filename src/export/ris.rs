@@ -0,0 +1,149 @@
+use std::io::Write;
+
+use crate::export::{plain, split_name};
+use crate::models::ArxivResult;
+
+fn push_tag(record: &mut String, tag: &str, value: &str) {
+    // Tag lines end in CRLF per the RIS specification.
+    record.push_str(&format!("{}  - {}\r\n", tag, value));
+}
+
+impl ArxivResult {
+    /// A RIS record: `JOUR` if the journal reference names a journal, `UNPB`
+    /// (unpublished work) otherwise. The arXiv id goes into `AN`.
+    pub fn to_ris(&self) -> String {
+        let journal_ref = self.journal_ref_info();
+        let is_article = journal_ref
+            .as_ref()
+            .is_some_and(|journal_ref| journal_ref.journal.is_some());
+        let record_type = if is_article { "JOUR" } else { "UNPB" };
+        let mut record = String::new();
+
+        push_tag(&mut record, "TY", record_type);
+        push_tag(&mut record, "TI", &plain(&self.title));
+        for author in &self.authors {
+            let name = split_name(author);
+            if name.given.is_empty() {
+                push_tag(&mut record, "AU", &name.family);
+            } else {
+                push_tag(
+                    &mut record,
+                    "AU",
+                    &format!("{}, {}", name.family, name.given),
+                );
+            }
+        }
+
+        let year = journal_ref
+            .as_ref()
+            .and_then(|journal_ref| journal_ref.year)
+            .unwrap_or(self.published.year());
+        push_tag(&mut record, "PY", &year.to_string());
+        push_tag(
+            &mut record,
+            "DA",
+            &format!(
+                "{:04}/{:02}/{:02}/",
+                self.published.year(),
+                self.published.month() as u8,
+                self.published.day()
+            ),
+        );
+
+        if let Some(journal_ref) = &journal_ref {
+            if let Some(journal) = &journal_ref.journal {
+                push_tag(&mut record, "JO", journal);
+            }
+            if let Some(volume) = &journal_ref.volume {
+                push_tag(&mut record, "VL", volume);
+            }
+            if let Some(issue) = &journal_ref.issue {
+                push_tag(&mut record, "IS", issue);
+            }
+            if let Some(pages) = &journal_ref.pages {
+                let (start, end) = pages.split_once('-').unwrap_or((pages, ""));
+                push_tag(&mut record, "SP", start);
+                if !end.is_empty() {
+                    push_tag(&mut record, "EP", end.trim_start_matches('-'));
+                }
+            }
+        }
+        if !is_article {
+            push_tag(&mut record, "PB", "arXiv");
+        }
+
+        push_tag(&mut record, "AB", &plain(&self.summary));
+        if let Some(doi) = &self.doi {
+            push_tag(&mut record, "DO", doi.trim());
+        }
+        push_tag(&mut record, "UR", &self.id);
+        if let Some(id) = self.arxiv_id() {
+            push_tag(&mut record, "AN", &format!("arXiv:{}", id.base()));
+        }
+        for category in &self.categories {
+            push_tag(&mut record, "KW", category.as_str());
+        }
+        if let Some(comment) = &self.comment {
+            push_tag(&mut record, "N1", &plain(comment));
+        }
+        push_tag(&mut record, "ER", "");
+
+        record
+    }
+}
+
+/// Writes the results as one RIS file.
+pub fn write_ris<'a, W: Write>(
+    mut writer: W,
+    results: impl IntoIterator<Item = &'a ArxivResult>,
+) -> anyhow::Result<()> {
+    for result in results {
+        writer.write_all(result.to_ris().as_bytes())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_to_ris() {
+        let results = fixture();
+
+        let preprint = results[0].to_ris();
+        let lines = preprint.split("\r\n").collect::<Vec<_>>();
+        assert_eq!(lines[0], "TY  - UNPB");
+        assert_eq!(lines[2], "AU  - Zeng, Shenglai");
+        assert!(lines.contains(&"DA  - 2024/02/23/"));
+        assert!(lines.contains(&"PB  - arXiv"));
+        assert!(lines.contains(&"AN  - arXiv:2402.16893"));
+        assert!(lines.contains(&"KW  - cs.AI"));
+        assert!(preprint.ends_with("ER  - \r\n"));
+
+        let article = results[1].to_ris();
+        assert!(article.starts_with("TY  - JOUR\r\n"));
+        assert!(article.contains("PY  - 1994\r\n"));
+        assert!(article.contains("JO  - Phys. Rev. Lett.\r\nVL  - 72\r\nSP  - 957\r\n"));
+        assert!(article.contains("DO  - 10.1103/PhysRevLett.72.957\r\n"));
+
+        let mut result = results[1].clone();
+        result.journal_ref = Some("vol. 12, pp. 45-60, 2018".to_string());
+        let record = result.to_ris();
+        assert!(record.starts_with("TY  - UNPB\r\n"));
+        assert!(record.contains("PB  - arXiv\r\n"));
+        assert!(!record.contains("JO  - "));
+    }
+
+    #[test]
+    fn test_write_ris() {
+        let mut output = vec![];
+        write_ris(&mut output, &fixture()).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("TY  - ").count(), 3);
+        assert_eq!(output.matches("ER  - ").count(), 3);
+    }
+}
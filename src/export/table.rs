@@ -0,0 +1,227 @@
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+
+use crate::export::plain;
+use crate::models::ArxivResult;
+
+/// The columns of [`TableRow`], in order.
+pub const TABLE_COLUMNS: [&str; 14] = [
+    "arxiv_id",
+    "version",
+    "title",
+    "authors",
+    "summary",
+    "primary_category",
+    "categories",
+    "published",
+    "updated",
+    "doi",
+    "journal_ref",
+    "comment",
+    "pdf_url",
+    "url",
+];
+
+/// Separates list entries in CSV cells.
+const LIST_SEPARATOR: &str = "; ";
+
+/// A result flattened into a fixed set of columns for dataframes and SQL
+/// engines. Titles and abstracts have their line breaks collapsed; dates are
+/// RFC 3339 strings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableRow {
+    /// The identifier without version, e.g. `2402.16893`; the raw id if it
+    /// could not be parsed.
+    pub arxiv_id: String,
+    pub version: Option<u32>,
+    pub title: String,
+    pub authors: Vec<String>,
+    pub summary: String,
+    pub primary_category: String,
+    pub categories: Vec<String>,
+    pub published: String,
+    pub updated: String,
+    pub doi: Option<String>,
+    pub journal_ref: Option<String>,
+    pub comment: Option<String>,
+    pub pdf_url: Option<String>,
+    pub url: String,
+}
+
+impl TableRow {
+    pub fn from_result(result: &ArxivResult) -> anyhow::Result<Self> {
+        let id = result.arxiv_id();
+
+        Ok(Self {
+            arxiv_id: id
+                .as_ref()
+                .map_or_else(|| result.id.clone(), |id| id.base().to_string()),
+            version: id.and_then(|id| id.version()),
+            title: plain(&result.title),
+            authors: result.authors.clone(),
+            summary: plain(&result.summary),
+            primary_category: result.primary_category.to_string(),
+            categories: result
+                .categories
+                .iter()
+                .map(|category| category.to_string())
+                .collect(),
+            published: result.published.format(&Rfc3339)?,
+            updated: result.updated.format(&Rfc3339)?,
+            doi: result.doi.clone(),
+            journal_ref: result.journal_ref.as_deref().map(plain),
+            comment: result.comment.as_deref().map(plain),
+            pdf_url: result.pdf_url.clone(),
+            url: result.id.clone(),
+        })
+    }
+
+    fn csv_record(&self) -> [String; 14] {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+
+        [
+            self.arxiv_id.clone(),
+            self.version.map(|v| v.to_string()).unwrap_or_default(),
+            self.title.clone(),
+            self.authors.join(LIST_SEPARATOR),
+            self.summary.clone(),
+            self.primary_category.clone(),
+            self.categories.join(LIST_SEPARATOR),
+            self.published.clone(),
+            self.updated.clone(),
+            optional(&self.doi),
+            optional(&self.journal_ref),
+            optional(&self.comment),
+            optional(&self.pdf_url),
+            self.url.clone(),
+        ]
+    }
+}
+
+/// Streams results as CSV with a header row of [`TABLE_COLUMNS`]. Authors
+/// and categories are joined with `; `.
+pub struct CsvWriter<W: Write> {
+    writer: csv::Writer<W>,
+    has_header: bool,
+}
+
+impl<W: Write> CsvWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: csv::Writer::from_writer(writer),
+            has_header: false,
+        }
+    }
+
+    pub fn write(&mut self, result: &ArxivResult) -> anyhow::Result<()> {
+        if !self.has_header {
+            self.writer.write_record(TABLE_COLUMNS)?;
+            self.has_header = true;
+        }
+        self.writer
+            .write_record(TableRow::from_result(result)?.csv_record())?;
+
+        Ok(())
+    }
+
+    pub fn write_all<'a>(
+        &mut self,
+        results: impl IntoIterator<Item = &'a ArxivResult>,
+    ) -> anyhow::Result<()> {
+        results
+            .into_iter()
+            .try_for_each(|result| self.write(result))
+    }
+
+    /// Flushes buffered rows and returns the underlying writer.
+    pub fn into_inner(self) -> anyhow::Result<W> {
+        self.writer
+            .into_inner()
+            .map_err(|e| anyhow::anyhow!("Failed to flush CSV: {}", e.error()))
+    }
+}
+
+/// Streams results as JSON Lines, one [`TableRow`] object per line.
+pub struct JsonLinesWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn write(&mut self, result: &ArxivResult) -> anyhow::Result<()> {
+        serde_json::to_writer(&mut self.writer, &TableRow::from_result(result)?)?;
+        self.writer.write_all(b"\n")?;
+
+        Ok(())
+    }
+
+    pub fn write_all<'a>(
+        &mut self,
+        results: impl IntoIterator<Item = &'a ArxivResult>,
+    ) -> anyhow::Result<()> {
+        results
+            .into_iter()
+            .try_for_each(|result| self.write(result))
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_table_row() {
+        let row = TableRow::from_result(&fixture()[1]).unwrap();
+        assert_eq!(row.arxiv_id, "hep-th/9901001");
+        assert_eq!(row.version, Some(2));
+        assert_eq!(
+            row.title,
+            "Black Hole Entropy and the Dimensional Continuation of the Gauss-Bonnet Theorem"
+        );
+        assert_eq!(row.categories, vec!["hep-th", "gr-qc"]);
+        assert_eq!(row.published, "1999-01-04T17:02:43Z");
+    }
+
+    #[test]
+    fn test_csv() {
+        let mut writer = CsvWriter::new(vec![]);
+        writer.write_all(&fixture()).unwrap();
+        let output = writer.into_inner().unwrap();
+
+        let mut reader = csv::Reader::from_reader(output.as_slice());
+        assert_eq!(reader.headers().unwrap(), TABLE_COLUMNS.as_slice());
+
+        let records = reader.records().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(&records[0][0], "2402.16893");
+        assert_eq!(&records[0][3], "Shenglai Zeng; Jiankun Zhang; Pengfei He");
+        assert_eq!(&records[1][9], "10.1103/PhysRevLett.72.957");
+        assert_eq!(&records[0][9], "");
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let results = fixture();
+        let mut writer = JsonLinesWriter::new(vec![]);
+        writer.write_all(&results).unwrap();
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+
+        let rows = output
+            .lines()
+            .map(|line| serde_json::from_str::<TableRow>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2], TableRow::from_result(&results[2]).unwrap());
+        assert_eq!(rows[0].authors.len(), 3);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::category::Category;
+use crate::models::ArxivResult;
+
+/// Client-side filter applied to every page fetched by
+/// [`crate::ArxivClient::search_pages`].
+#[derive(Debug, Clone, Default)]
+pub struct ResultFilter {
+    cross_lists_excluded: Vec<Category>,
+    withdrawn_excluded: bool,
+}
+
+impl ResultFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops papers that are merely cross-listed in `category`.
+    pub fn with_cross_lists_excluded(mut self, category: Category) -> Self {
+        self.cross_lists_excluded.push(category);
+        self
+    }
+
+    /// Drops papers that look withdrawn, see [`ArxivResult::is_withdrawn`].
+    pub fn with_withdrawn_excluded(mut self) -> Self {
+        self.withdrawn_excluded = true;
+        self
+    }
+
+    pub fn matches(&self, result: &ArxivResult) -> bool {
+        if self.withdrawn_excluded && result.is_withdrawn() {
+            return false;
+        }
+
+        !self
+            .cross_lists_excluded
+            .iter()
+            .any(|category| result.is_cross_list_for(category))
+    }
+
+    pub fn apply(&self, results: Vec<ArxivResult>) -> Vec<ArxivResult> {
+        results
+            .into_iter()
+            .filter(|result| self.matches(result))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_default_keeps_everything() {
+        assert_eq!(ResultFilter::new().apply(fixture()).len(), 3);
+    }
+
+    #[test]
+    fn test_cross_lists_excluded() {
+        let filter = ResultFilter::new().with_cross_lists_excluded(Category::new("cs.CL"));
+        let results = filter.apply(fixture());
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "http://arxiv.org/abs/hep-th/9901001v2");
+    }
+
+    #[test]
+    fn test_withdrawn_excluded() {
+        let results = ResultFilter::new()
+            .with_withdrawn_excluded()
+            .apply(fixture());
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| !result.is_withdrawn()));
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+use crate::category::Category;
+
+const URL_PREFIXES: &[&str] = &[
+    "http://arxiv.org/abs/",
+    "https://arxiv.org/abs/",
+    "http://export.arxiv.org/abs/",
+    "https://export.arxiv.org/abs/",
+    "http://arxiv.org/pdf/",
+    "https://arxiv.org/pdf/",
+    "arxiv.org/abs/",
+];
+
+/// An arXiv identifier, either new style (`2402.16893v1`) or old style
+/// (`hep-th/9901001v2`, `math.AG/0101001`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ArxivId {
+    base: String,
+    version: Option<u32>,
+}
+
+fn is_new_style(base: &str) -> bool {
+    match base.split_once('.') {
+        Some((yymm, number)) => {
+            yymm.len() == 4
+                && (number.len() == 4 || number.len() == 5)
+                && yymm.chars().all(|c| c.is_ascii_digit())
+                && number.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+fn is_old_style(base: &str) -> bool {
+    match base.split_once('/') {
+        Some((archive, number)) => {
+            let (archive, subject) = archive.split_once('.').unwrap_or((archive, ""));
+            !archive.is_empty()
+                && archive.chars().all(|c| c.is_ascii_lowercase() || c == '-')
+                && subject.chars().all(|c| c.is_ascii_uppercase())
+                && number.len() == 7
+                && number.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+impl ArxivId {
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        let mut id = s.trim();
+        for prefix in URL_PREFIXES {
+            if let Some(stripped) = id.strip_prefix(prefix) {
+                id = stripped;
+                break;
+            }
+        }
+        if id
+            .get(..6)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("arxiv:"))
+        {
+            id = &id[6..];
+        }
+        let id = id.strip_suffix(".pdf").unwrap_or(id);
+        let invalid = || anyhow!("Invalid arXiv identifier: {}", s);
+
+        let (base, version) = match id.rfind('v') {
+            Some(pos)
+                if pos + 1 < id.len() && id[pos + 1..].chars().all(|c| c.is_ascii_digit()) =>
+            {
+                let version = id[pos + 1..].parse::<u32>().map_err(|_| invalid())?;
+                (&id[..pos], Some(version))
+            }
+            _ => (id, None),
+        };
+
+        if !is_new_style(base) && !is_old_style(base) {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            base: base.to_string(),
+            version,
+        })
+    }
+
+    /// The identifier without its version suffix.
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+
+    pub fn with_version(&self, version: u32) -> Self {
+        Self {
+            base: self.base.clone(),
+            version: Some(version),
+        }
+    }
+
+    pub fn without_version(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            version: None,
+        }
+    }
+
+    pub fn is_old_style(&self) -> bool {
+        self.base.contains('/')
+    }
+
+    /// Old-style identifiers carry their archive (and sometimes subject
+    /// class), e.g. `hep-th` for `hep-th/9901001` and `math.AG` for
+    /// `math.AG/0101001`. New-style identifiers imply nothing.
+    pub fn implied_category(&self) -> Option<Category> {
+        self.base
+            .split_once('/')
+            .map(|(category, _)| Category::new(category))
+    }
+
+    pub fn abs_url(&self) -> String {
+        format!("http://arxiv.org/abs/{}", self)
+    }
+
+    pub fn pdf_url(&self) -> String {
+        format!("http://arxiv.org/pdf/{}", self)
+    }
+}
+
+impl FromStr for ArxivId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for ArxivId {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(&s)
+    }
+}
+
+impl From<ArxivId> for String {
+    fn from(id: ArxivId) -> Self {
+        id.to_string()
+    }
+}
+
+impl Display for ArxivId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.version {
+            Some(version) => write!(f, "{}v{}", self.base, version),
+            None => write!(f, "{}", self.base),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_new_style() {
+        let id = ArxivId::parse("http://arxiv.org/abs/2402.16893v1").unwrap();
+        assert_eq!(id.base(), "2402.16893");
+        assert_eq!(id.version(), Some(1));
+        assert_eq!(id.to_string(), "2402.16893v1");
+        assert_eq!(id.implied_category(), None);
+
+        let id = ArxivId::parse("arXiv:0704.0001").unwrap();
+        assert_eq!(id.base(), "0704.0001");
+        assert_eq!(id.version(), None);
+    }
+
+    #[test]
+    fn test_old_style() {
+        let id = ArxivId::parse("http://arxiv.org/abs/hep-th/9901001v2").unwrap();
+        assert_eq!(id.base(), "hep-th/9901001");
+        assert_eq!(id.version(), Some(2));
+        assert_eq!(id.implied_category(), Some(Category::new("hep-th")));
+
+        let id = ArxivId::parse("math.AG/0101001").unwrap();
+        assert_eq!(id.implied_category(), Some(Category::new("math.AG")));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(ArxivId::parse("not an id").is_err());
+        assert!(ArxivId::parse("2402.168").is_err());
+        assert!(ArxivId::parse("hep-th/99").is_err());
+        assert!(ArxivId::parse("abcdeé1234").is_err());
+        assert!(ArxivId::parse("arXivé").is_err());
+        assert_eq!(
+            ArxivId::parse("2402.16893v99999999999")
+                .unwrap_err()
+                .to_string(),
+            "Invalid arXiv identifier: 2402.16893v99999999999"
+        );
+    }
+}
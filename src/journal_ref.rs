@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::ArxivResult;
+
+/// Best-effort structure extracted from the free-text `journal_ref` field,
+/// e.g. `Phys. Rev. D 99, 123456 (2019)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct JournalRef {
+    pub raw: String,
+    pub journal: Option<String>,
+    pub volume: Option<String>,
+    pub issue: Option<String>,
+    /// A page range (`17-29`) or an article number (`123456`).
+    pub pages: Option<String>,
+    pub year: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Marker {
+    Volume,
+    Issue,
+    Pages,
+}
+
+fn marker(token: &str) -> Option<(Marker, &str)> {
+    const MARKERS: &[(&str, Marker)] = &[
+        ("volume", Marker::Volume),
+        ("vol.", Marker::Volume),
+        ("vol", Marker::Volume),
+        ("issue", Marker::Issue),
+        ("no.", Marker::Issue),
+        ("no", Marker::Issue),
+        ("num.", Marker::Issue),
+        ("pages", Marker::Pages),
+        ("pp.", Marker::Pages),
+        ("pp", Marker::Pages),
+        ("p.", Marker::Pages),
+        ("art.", Marker::Pages),
+        ("article", Marker::Pages),
+    ];
+
+    let lower = token.to_ascii_lowercase();
+    MARKERS.iter().find_map(|(prefix, marker)| {
+        if !lower.starts_with(prefix) {
+            return None;
+        }
+        let rest = &token[prefix.len()..];
+        (rest.is_empty() || rest.starts_with(|c: char| c.is_ascii_digit()))
+            .then_some((*marker, rest))
+    })
+}
+
+fn as_year(token: &str) -> Option<i32> {
+    let digits = token.trim_matches(|c: char| !c.is_ascii_digit());
+    let is_bare = token.len() == digits.len() || (token.starts_with('(') && token.ends_with(')'));
+    if digits.len() != 4 || !is_bare {
+        return None;
+    }
+
+    digits
+        .parse::<i32>()
+        .ok()
+        .filter(|year| (1900..2100).contains(year))
+}
+
+/// Splits `21(140):1-67` into volume, issue and pages.
+fn split_volume(token: &str, parsed: &mut JournalRef) {
+    let (volume, pages) = token.split_once(':').unwrap_or((token, ""));
+    let (volume, issue) = match volume.split_once('(') {
+        Some((volume, issue)) => (volume, issue.trim_end_matches(')')),
+        None => (volume, ""),
+    };
+
+    parsed.volume = Some(volume.to_string());
+    if !issue.is_empty() {
+        parsed.issue = Some(issue.to_string());
+    }
+    if !pages.is_empty() {
+        parsed.pages = Some(pages.to_string());
+    }
+}
+
+impl JournalRef {
+    pub fn parse(raw: &str) -> Self {
+        let mut parsed = JournalRef {
+            raw: raw.trim().to_string(),
+            ..Default::default()
+        };
+
+        let mut tokens: Vec<&str> = raw
+            .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+            .filter(|token| !token.is_empty())
+            .collect();
+
+        // A parenthesized year wins; otherwise take the last bare year that
+        // does not follow a volume marker.
+        let year_pos = tokens
+            .iter()
+            .position(|token| token.starts_with('(') && as_year(token).is_some())
+            .or_else(|| {
+                tokens
+                    .iter()
+                    .rposition(|token| !token.starts_with('(') && as_year(token).is_some())
+                    .filter(|pos| {
+                        *pos == 0
+                            || !marker(tokens[*pos - 1]).is_some_and(|(marker, rest)| {
+                                marker == Marker::Volume && rest.is_empty()
+                            })
+                    })
+            });
+        if let Some(pos) = year_pos {
+            parsed.year = as_year(tokens.remove(pos));
+        }
+
+        let mut journal = vec![];
+        let mut pending: Option<Marker> = None;
+
+        for token in tokens {
+            if let Some(marker) = pending.take() {
+                match marker {
+                    Marker::Volume => split_volume(token, &mut parsed),
+                    Marker::Issue => parsed.issue = Some(token.to_string()),
+                    Marker::Pages => parsed.pages = Some(token.to_string()),
+                }
+                continue;
+            }
+
+            if let Some((marker, rest)) = marker(token) {
+                if rest.is_empty() {
+                    pending = Some(marker);
+                    continue;
+                }
+                match marker {
+                    Marker::Volume => split_volume(rest, &mut parsed),
+                    Marker::Issue => parsed.issue = Some(rest.to_string()),
+                    Marker::Pages => parsed.pages = Some(rest.to_string()),
+                }
+                continue;
+            }
+
+            let has_digit = token.chars().any(|c| c.is_ascii_digit());
+            if !has_digit && parsed.volume.is_none() && parsed.pages.is_none() {
+                journal.push(token);
+            } else if has_digit && parsed.volume.is_none() {
+                split_volume(token.trim_end_matches('.'), &mut parsed);
+            } else if has_digit && parsed.pages.is_none() {
+                parsed.pages = Some(token.trim_end_matches('.').to_string());
+            }
+        }
+
+        if !journal.is_empty() {
+            parsed.journal = Some(journal.join(" "));
+        }
+
+        parsed
+    }
+
+    /// A normalized journal name for grouping, so `Phys.Rev.D` and
+    /// `Phys. Rev. D` end up together.
+    pub fn venue_key(&self) -> Option<String> {
+        self.journal.as_ref().map(|journal| {
+            journal
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(|c| c.to_lowercase())
+                .collect()
+        })
+    }
+}
+
+impl ArxivResult {
+    pub fn journal_ref_info(&self) -> Option<JournalRef> {
+        self.journal_ref.as_deref().map(JournalRef::parse)
+    }
+
+    /// Whether arXiv knows of a published version (a DOI or a journal reference).
+    pub fn is_published(&self) -> bool {
+        self.doi.is_some() || self.journal_ref.is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    fn some(s: &str) -> Option<String> {
+        Some(s.to_string())
+    }
+
+    fn expected(
+        raw: &str,
+        journal: &str,
+        volume: &str,
+        issue: Option<&str>,
+        pages: &str,
+        year: i32,
+    ) -> JournalRef {
+        JournalRef {
+            raw: raw.to_string(),
+            journal: some(journal),
+            volume: some(volume),
+            issue: issue.map(|issue| issue.to_string()),
+            pages: some(pages),
+            year: Some(year),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let cases = [
+            (
+                "Phys. Rev. D 99, 123456 (2019)",
+                "Phys. Rev. D",
+                "99",
+                None,
+                "123456",
+                2019,
+            ),
+            (
+                "Eur.Phys.J. C31 (2003) 17-29",
+                "Eur.Phys.J.",
+                "C31",
+                None,
+                "17-29",
+                2003,
+            ),
+            ("JHEP 0901:012,2009", "JHEP", "0901", None, "012", 2009),
+            (
+                "J. Mach. Learn. Res. 21(140):1-67, 2020",
+                "J. Mach. Learn. Res.",
+                "21",
+                Some("140"),
+                "1-67",
+                2020,
+            ),
+            (
+                "Journal of Foo, vol. 12, no. 3, pp. 45-60, 2018",
+                "Journal of Foo",
+                "12",
+                Some("3"),
+                "45-60",
+                2018,
+            ),
+        ];
+
+        for (raw, journal, volume, issue, pages, year) in cases {
+            assert_eq!(
+                JournalRef::parse(raw),
+                expected(raw, journal, volume, issue, pages, year)
+            );
+        }
+    }
+
+    #[test]
+    fn test_year_after_venue() {
+        let parsed = JournalRef::parse("Proc. NeurIPS 2020, pp. 1-10");
+        assert_eq!(parsed.journal, some("Proc. NeurIPS"));
+        assert_eq!(parsed.volume, None);
+        assert_eq!(parsed.pages, some("1-10"));
+        assert_eq!(parsed.year, Some(2020));
+
+        let parsed = JournalRef::parse("Foo Letters, vol. 2001, pp. 3-4");
+        assert_eq!(parsed.volume, some("2001"));
+        assert_eq!(parsed.year, None);
+    }
+
+    #[test]
+    fn test_unparsable() {
+        let parsed = JournalRef::parse("Proceedings of the workshop");
+        assert_eq!(parsed.journal, some("Proceedings of the workshop"));
+        assert_eq!(parsed.year, None);
+        assert_eq!(parsed.raw, "Proceedings of the workshop");
+    }
+
+    #[test]
+    fn test_venue_key() {
+        assert_eq!(
+            JournalRef::parse("Phys.Rev.D 99 (2019) 1").venue_key(),
+            JournalRef::parse("Phys. Rev. D 100, 2 (2020)").venue_key()
+        );
+    }
+
+    #[test]
+    fn test_journal_ref_info() {
+        let results = fixture();
+        assert!(results[0].journal_ref_info().is_none());
+        assert!(!results[0].is_published());
+
+        let parsed = results[1].journal_ref_info().unwrap();
+        assert_eq!(parsed.journal, some("Phys. Rev. Lett."));
+        assert_eq!(parsed.volume, some("72"));
+        assert_eq!(parsed.pages, some("957"));
+        assert_eq!(parsed.year, Some(1994));
+        assert!(results[1].is_published());
+    }
+}
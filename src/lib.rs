@@ -1,20 +1,79 @@
+mod category;
+mod clock;
+mod comment;
+mod cross_list;
+mod diff;
+mod export;
+mod filter;
+mod id;
+mod journal_ref;
 mod models;
+mod parse;
 mod query;
+mod raw;
 mod search_query;
-
-pub use models::ArxivResult;
+mod stream;
+mod updates;
+mod versions;
+mod withdrawal;
+
+pub use category::{taxonomy, Category, CategoryGroup, CategoryInfo};
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use comment::{CommentInfo, VenueMention, VenueStatus};
+pub use cross_list::CrossListStatus;
+pub use diff::{word_diff, FieldChange, ResultDiff, WordChange};
+#[cfg(feature = "arrow")]
+pub use export::{table_schema, to_record_batch, ParquetWriter};
+pub use export::{
+    to_atom_feed, to_csl_json, to_json_feed, to_json_ld_graph, to_rss_feed, write_ris,
+    BibtexWriter, CitationKey, CslDate, CslItem, CslName, CsvWriter, Digest, DigestGrouping,
+    FeedMetadata, JsonLinesWriter, NoteVault, TableRow, DEFAULT_NOTE_MARKER, TABLE_COLUMNS,
+};
+pub use filter::ResultFilter;
+pub use id::ArxivId;
+pub use journal_ref::JournalRef;
+pub use models::{ArxivResult, Author, Link};
+pub use parse::{
+    parse_feed, parse_feed_reader, parse_feed_with_mode, ParseDiagnostic, ParseMode, SearchResults,
+};
 pub use query::*;
-pub use search_query::{RangeField, SearchField, SearchPredicate, SearchRange, SearchTerm};
+pub use raw::{DirectoryRecorder, RawResponse, ResponseRecorder};
+pub use search_query::{
+    ISearchQuery, QueryParseError, RangeField, SearchField, SearchPredicate, SearchRange,
+    SearchTerm,
+};
+pub use stream::{FeedReader, FeedStream};
+pub use updates::{PaperChange, PaperUpdate, TrackedPaper, UpdateReport};
+pub use versions::PaperVersion;
+
+use std::sync::Arc;
 
 use anyhow::anyhow;
+use futures_util::TryStreamExt as _;
+use tokio::io::AsyncBufRead;
+use tokio_util::io::StreamReader;
 
 const BASE_URL: &str = "http://export.arxiv.org/api/query";
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ArxivClient {
     client: reqwest::Client,
     interval: std::time::Duration,
     n_retries: usize,
+    parse_mode: ParseMode,
+    recorder: Option<Arc<dyn ResponseRecorder>>,
+}
+
+impl std::fmt::Debug for ArxivClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArxivClient")
+            .field("client", &self.client)
+            .field("interval", &self.interval)
+            .field("n_retries", &self.n_retries)
+            .field("parse_mode", &self.parse_mode)
+            .field("recorder", &self.recorder.is_some())
+            .finish()
+    }
 }
 
 impl Default for ArxivClient {
@@ -23,6 +82,8 @@ impl Default for ArxivClient {
             client: reqwest::Client::new(),
             interval: std::time::Duration::from_secs(3),
             n_retries: 3,
+            parse_mode: ParseMode::default(),
+            recorder: None,
         }
     }
 }
@@ -33,41 +94,127 @@ impl ArxivClient {
             client: reqwest::Client::new(),
             interval,
             n_retries,
+            parse_mode: ParseMode::default(),
+            recorder: None,
+        }
+    }
+
+    pub fn with_parse_mode(mut self, parse_mode: ParseMode) -> Self {
+        self.parse_mode = parse_mode;
+        self
+    }
+
+    /// Hands every buffered response to `recorder` before it is parsed, e.g.
+    /// a [`DirectoryRecorder`] to archive responses for later replay.
+    /// Responses consumed through [`ArxivClient::search_stream`] are not
+    /// recorded. Recorder errors are handed to
+    /// [`ResponseRecorder::record_failed`] and do not fail the search.
+    pub fn with_recorder(mut self, recorder: impl ResponseRecorder + 'static) -> Self {
+        self.recorder = Some(Arc::new(recorder));
+        self
+    }
+
+    pub async fn search<S: ToString>(&self, query: ArxivQuery<S>) -> anyhow::Result<SearchResults> {
+        self.fetch(&query.to_url(BASE_URL)?).await
+    }
+
+    /// Returns the response without parsing it, for debugging and archival.
+    /// Server errors are retried like failed requests; other error statuses
+    /// are returned as they are.
+    pub async fn search_raw<S: ToString>(
+        &self,
+        query: ArxivQuery<S>,
+    ) -> anyhow::Result<RawResponse> {
+        self.fetch_raw(&query.to_url(BASE_URL)?).await
+    }
+
+    /// Fetches up to `max_pages` consecutive pages starting at the query's
+    /// `start`, waiting `interval` between requests. Paging stops early once
+    /// arXiv returns a short page.
+    pub async fn search_pages<S: ToString>(
+        &self,
+        mut query: ArxivQuery<S>,
+        max_pages: usize,
+        filter: &ResultFilter,
+    ) -> anyhow::Result<SearchResults> {
+        let mut results = SearchResults::default();
+
+        for page in 0..max_pages {
+            if page > 0 {
+                tokio::time::sleep(self.interval).await;
+            }
+
+            let mut page_results = self.fetch(&query.to_url(BASE_URL)?).await?;
+            let n_fetched = page_results.results.len() + page_results.diagnostics.len();
+            page_results.results = filter.apply(page_results.results);
+            results.extend(page_results);
+
+            if n_fetched < query.max_results() {
+                break;
+            }
+            query = query.next_page_query();
         }
+
+        Ok(results)
     }
 
-    pub async fn search<S: ToString>(
+    /// Parses the response while it downloads instead of buffering the whole
+    /// body first; useful for large `max_results`. Request errors are not
+    /// retried once the body has started streaming.
+    pub async fn search_stream<S: ToString>(
         &self,
         query: ArxivQuery<S>,
-    ) -> anyhow::Result<Vec<ArxivResult>> {
+    ) -> anyhow::Result<FeedStream<impl AsyncBufRead + Unpin>> {
+        let response = self
+            .client
+            .get(&query.to_url(BASE_URL)?)
+            .send()
+            .await?
+            .error_for_status()?;
+        let body = response.bytes_stream().map_err(std::io::Error::other);
+
+        Ok(FeedStream::new(StreamReader::new(body)).with_mode(self.parse_mode))
+    }
+
+    async fn fetch(&self, url: &str) -> anyhow::Result<SearchResults> {
+        self.fetch_raw(url).await?.parse(self.parse_mode)
+    }
+
+    async fn fetch_raw(&self, url: &str) -> anyhow::Result<RawResponse> {
         let mut errors = vec![];
 
         for _ in 0..self.n_retries {
-            let response = self.client.get(&query.to_url(BASE_URL)?).send().await;
+            let response = self.client.get(url).send().await;
 
             if let Err(e) = response {
-                errors.push(e);
+                errors.push(e.to_string());
                 tokio::time::sleep(self.interval).await;
                 continue;
             }
 
             let response = response.unwrap();
 
-            let text = response.text().await?;
-            let feed = quick_xml::de::from_str::<models::Feed>(&text)?;
+            let raw = RawResponse {
+                url: url.to_string(),
+                status: response.status(),
+                headers: response.headers().clone(),
+                body: response.bytes().await?.to_vec(),
+            };
+            if let Some(recorder) = &self.recorder {
+                if let Err(e) = recorder.record(&raw) {
+                    recorder.record_failed(&raw, e);
+                }
+            }
+            if raw.is_transient_error() {
+                errors.push(raw.error_for_status().unwrap_err().to_string());
+                tokio::time::sleep(self.interval).await;
+                continue;
+            }
 
-            return Ok(feed
-                .entries_
-                .into_iter()
-                .map(ArxivResult::from_entry)
-                .collect());
+            return Ok(raw);
         }
 
-        let err_msgs = errors
-            .into_iter()
-            .map(|e| e.to_string())
-            .collect::<Vec<String>>()
-            .join("\n");
+        let err_msgs = errors.join("\n");
 
         Err(anyhow!(
             "Failed to fetch data from Arxiv after {} retries\n{}",
@@ -164,4 +311,33 @@ mod test {
 
         assert_eq!(results.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_search_pages() {
+        let client = ArxivClient::new(std::time::Duration::from_secs(1), 3);
+        let category = Category::new("cs.CL");
+        let query = ArxivQuery::default()
+            .with_search_query(SearchTerm::category(&category))
+            .with_max_results(5);
+        let filter = ResultFilter::new().with_cross_lists_excluded(category.clone());
+
+        let results = client.search_pages(query, 2, &filter).await.unwrap();
+        assert!(results
+            .iter()
+            .all(|result| !result.is_cross_list_for(&category)));
+    }
+
+    #[tokio::test]
+    async fn test_versions() {
+        let client = ArxivClient::new(std::time::Duration::from_secs(1), 3);
+        let id = ArxivId::parse("1706.03762").unwrap();
+
+        let versions = client.versions(&id).await.unwrap();
+        assert!(versions.len() >= 5);
+        assert_eq!(versions[0].version, 1);
+        assert!(versions.windows(2).all(|pair| pair[0].date <= pair[1].date));
+
+        let v2 = client.fetch_version(&id, 2).await.unwrap();
+        assert_eq!(v2.arxiv_id().unwrap().version(), Some(2));
+    }
 }
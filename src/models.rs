@@ -1,36 +1,34 @@
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+
 use serde::{Deserialize, Serialize};
-use time::serde::iso8601;
+use time::format_description::well_known::Iso8601;
 use time::OffsetDateTime;
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
-pub struct Feed {
-    #[serde(rename = "entry", default)]
-    pub entries_: Vec<Entry>,
-}
+use crate::category::Category;
+use crate::id::ArxivId;
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct Entry {
-    id: String,
-    title: String,
-    summary: String,
-    #[serde(with = "iso8601")]
-    updated: OffsetDateTime,
-    #[serde(with = "iso8601")]
-    published: OffsetDateTime,
+    pub(crate) id: Option<String>,
+    pub(crate) title: Option<String>,
+    pub(crate) summary: Option<String>,
+    pub(crate) updated: Option<String>,
+    pub(crate) published: Option<String>,
     #[serde(rename = "author", default)]
-    authors: Vec<Author>,
+    pub(crate) authors: Vec<EntryAuthor>,
     #[serde(rename = "link", default)]
-    links: Vec<Link>,
+    pub(crate) links: Vec<EntryLink>,
     #[serde(rename = "primary_category")]
-    primary_category: Category,
+    pub(crate) primary_category: Option<EntryCategory>,
     #[serde(rename = "category", default)]
-    categories: Vec<Category>,
+    pub(crate) categories: Vec<EntryCategory>,
     #[serde(rename = "doi")]
-    doi: Option<String>,
+    pub(crate) doi: Option<String>,
     #[serde(rename = "comment")]
-    comment: Option<String>,
+    pub(crate) comment: Option<String>,
     #[serde(rename = "journal_ref")]
-    journal_ref: Option<String>,
+    pub(crate) journal_ref: Option<String>,
 }
 
 impl Entry {
@@ -43,7 +41,10 @@ impl Entry {
         let ret = pdf_links.next().map(|link| link.href.clone());
 
         if pdf_links.next().is_some() {
-            eprintln!("Multiple pdf links found for entry: {}", self.id);
+            eprintln!(
+                "Multiple pdf links found for entry: {}",
+                self.id.as_deref().unwrap_or_default()
+            );
         }
 
         ret
@@ -51,78 +52,387 @@ impl Entry {
 }
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct EntryAuthor {
+    pub(crate) name: String,
+    #[serde(rename = "affiliation", default)]
+    pub(crate) affiliations: Vec<String>,
+}
+
+/// An author of a paper, with the affiliations arXiv knows of, if any.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Author {
-    name: String,
+    pub name: String,
+    pub affiliations: Vec<String>,
+}
+
+impl Author {
+    pub fn new<S: ToString>(name: S) -> Self {
+        Self {
+            name: name.to_string(),
+            affiliations: vec![],
+        }
+    }
+
+    pub fn with_affiliation<S: ToString>(mut self, affiliation: S) -> Self {
+        self.affiliations.push(affiliation.to_string());
+        self
+    }
+}
+
+impl Display for Author {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
-pub struct Link {
+pub struct EntryLink {
     #[serde(rename = "@title")]
-    title: Option<String>,
-    #[serde(rename = "@rel")]
-    rel: String,
+    pub(crate) title: Option<String>,
+    #[serde(rename = "@rel", default)]
+    pub(crate) rel: String,
     #[serde(rename = "@href")]
-    href: String,
+    pub(crate) href: String,
     #[serde(rename = "@type")]
-    content_type: Option<String>,
+    pub(crate) content_type: Option<String>,
+}
+
+/// A related resource of a paper, such as its abstract page, PDF or DOI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Link {
+    pub href: String,
+    /// `alternate` for the abstract page, `related` for the PDF and DOI.
+    #[serde(default)]
+    pub rel: String,
+    /// `pdf` or `doi` for the corresponding links.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// The MIME type, e.g. `application/pdf`.
+    #[serde(default)]
+    pub content_type: Option<String>,
+}
+
+impl From<EntryLink> for Link {
+    fn from(link: EntryLink) -> Self {
+        Self {
+            href: link.href,
+            rel: link.rel,
+            title: link.title,
+            content_type: link.content_type,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
-pub struct Category {
+pub struct EntryCategory {
     #[serde(rename = "@term")]
-    term: String,
+    pub(crate) term: String,
     #[serde(rename = "@scheme")]
-    scheme: String,
+    pub(crate) scheme: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FieldError {
+    pub(crate) field: &'static str,
+    pub(crate) reason: String,
 }
 
+fn required<T>(value: Option<T>, field: &'static str, errors: &mut Vec<FieldError>) -> Option<T> {
+    if value.is_none() {
+        errors.push(FieldError {
+            field,
+            reason: "missing".to_string(),
+        });
+    }
+    value
+}
+
+fn timestamp(
+    value: Option<String>,
+    field: &'static str,
+    errors: &mut Vec<FieldError>,
+) -> Option<OffsetDateTime> {
+    let value = required(value, field, errors)?;
+    match OffsetDateTime::parse(value.trim(), &Iso8601::DEFAULT) {
+        Ok(timestamp) => Some(timestamp),
+        Err(e) => {
+            errors.push(FieldError {
+                field,
+                reason: format!("invalid timestamp {:?}: {}", value, e),
+            });
+            None
+        }
+    }
+}
+
+/// Timestamps are written as RFC 3339 (`2024-02-23T18:35:15Z`). The extended
+/// ISO 8601 form written by earlier versions is still accepted.
+pub(crate) mod timestamp_format {
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use time::format_description::well_known::{Iso8601, Rfc3339};
+    use time::OffsetDateTime;
+
+    pub fn serialize<S: Serializer>(
+        timestamp: &OffsetDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let formatted = timestamp
+            .format(&Rfc3339)
+            .map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&formatted)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<OffsetDateTime, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        OffsetDateTime::parse(&value, &Rfc3339)
+            .or_else(|_| OffsetDateTime::parse(&value, &Iso8601::DEFAULT))
+            .map_err(D::Error::custom)
+    }
+}
+
+/// A single paper as returned by the API.
+///
+/// Serializes to a flat JSON object with the field names below; categories
+/// are plain strings and timestamps RFC 3339 strings. Two results are equal
+/// (and hash alike) when they refer to the same version of the same paper,
+/// regardless of the rest of their metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ArxivResult {
+    /// The abstract page URL, e.g. `http://arxiv.org/abs/2402.16893v1`.
     pub id: String,
     pub title: String,
     pub summary: String,
     pub authors: Vec<String>,
+    /// The affiliations of each author, in the order of `authors`.
+    #[serde(default)]
+    pub author_affiliations: Vec<Vec<String>>,
 
     pub doi: Option<String>,
     pub comment: Option<String>,
     pub journal_ref: Option<String>,
 
-    pub primary_category: String,
-    pub categories: Vec<String>,
+    pub primary_category: Category,
+    pub categories: Vec<Category>,
 
     pub pdf_url: Option<String>,
     pub links: Vec<Link>,
 
-    #[serde(with = "iso8601")]
+    #[serde(with = "timestamp_format")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub published: OffsetDateTime,
-    #[serde(with = "iso8601")]
+    #[serde(with = "timestamp_format")]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub updated: OffsetDateTime,
 }
 
 impl ArxivResult {
-    pub(crate) fn from_entry(entry: Entry) -> Self {
+    /// Converts a leniently deserialized entry, reporting every missing or
+    /// malformed required field instead of stopping at the first one.
+    pub(crate) fn try_from_entry(entry: Entry) -> Result<Self, Vec<FieldError>> {
+        let mut errors = vec![];
         let pdf_url = entry.get_pdf_url();
-        Self {
-            id: entry.id,
-            title: entry.title,
-            summary: entry.summary,
-            authors: entry
-                .authors
-                .into_iter()
-                .map(|author| author.name)
-                .collect(),
-            doi: entry.doi,
-            comment: entry.comment,
-            journal_ref: entry.journal_ref,
-            primary_category: entry.primary_category.term,
-            categories: entry
-                .categories
-                .into_iter()
-                .map(|category| category.term)
-                .collect(),
-            pdf_url,
-            links: entry.links,
-            published: entry.published,
-            updated: entry.updated,
+
+        let id = required(entry.id, "id", &mut errors);
+        let title = required(entry.title, "title", &mut errors);
+        let summary = required(entry.summary, "summary", &mut errors);
+        let published = timestamp(entry.published, "published", &mut errors);
+        let updated = timestamp(entry.updated, "updated", &mut errors);
+        let primary_category = required(
+            entry.primary_category.map(|category| category.term),
+            "primary_category",
+            &mut errors,
+        );
+
+        match (id, title, summary, published, updated, primary_category) {
+            (
+                Some(id),
+                Some(title),
+                Some(summary),
+                Some(published),
+                Some(updated),
+                Some(primary_category),
+            ) => Ok(Self {
+                id,
+                title,
+                summary,
+                authors: entry
+                    .authors
+                    .iter()
+                    .map(|author| author.name.clone())
+                    .collect(),
+                author_affiliations: entry
+                    .authors
+                    .into_iter()
+                    .map(|author| author.affiliations)
+                    .collect(),
+                doi: entry.doi,
+                comment: entry.comment,
+                journal_ref: entry.journal_ref,
+                primary_category: Category::new(primary_category),
+                categories: entry
+                    .categories
+                    .into_iter()
+                    .map(|category| Category::new(category.term))
+                    .collect(),
+                pdf_url,
+                links: entry.links.into_iter().map(Link::from).collect(),
+                published,
+                updated,
+            }),
+            _ => Err(errors),
         }
     }
+
+    /// The authors together with their affiliations.
+    pub fn authors_detailed(&self) -> Vec<Author> {
+        self.authors
+            .iter()
+            .enumerate()
+            .map(|(i, name)| Author {
+                name: name.clone(),
+                affiliations: self.author_affiliations.get(i).cloned().unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    pub fn arxiv_id(&self) -> Option<ArxivId> {
+        ArxivId::parse(&self.id).ok()
+    }
+
+    /// The base id and version, or the raw id if it is not an arXiv id.
+    fn identity(&self) -> Result<ArxivId, &str> {
+        self.arxiv_id().ok_or(self.id.as_str())
+    }
+
+    #[cfg(feature = "schemars")]
+    pub fn json_schema() -> schemars::schema::RootSchema {
+        schemars::schema_for!(ArxivResult)
+    }
+}
+
+impl PartialEq for ArxivResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+}
+
+impl Eq for ArxivResult {}
+
+impl Hash for ArxivResult {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.identity().hash(state);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+
+    pub(crate) const FEED: &str = include_str!("../testdata/feed.xml");
+
+    pub(crate) fn fixture() -> Vec<ArxivResult> {
+        crate::parse::parse_feed_str(FEED, crate::parse::ParseMode::Strict)
+            .unwrap()
+            .results
+    }
+
+    #[test]
+    fn test_from_entry() {
+        let results = fixture();
+        assert_eq!(results.len(), 3);
+
+        let result = &results[0];
+        assert_eq!(result.id, "http://arxiv.org/abs/2402.16893v1");
+        assert_eq!(result.authors.len(), 3);
+        assert!(result.author_affiliations[0].is_empty());
+        assert_eq!(
+            results[1].authors_detailed()[1],
+            Author::new("Claudio Teitelboim")
+                .with_affiliation("Centro de Estudios Cientificos de Santiago")
+                .with_affiliation("Institute for Advanced Study")
+        );
+        assert_eq!(result.primary_category, "cs.CR");
+        assert_eq!(
+            result.pdf_url.as_deref(),
+            Some("http://arxiv.org/pdf/2402.16893v1")
+        );
+
+        let id = result.arxiv_id().unwrap();
+        assert_eq!(id.base(), "2402.16893");
+        assert_eq!(id.version(), Some(1));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let results = fixture();
+        let json = serde_json::to_value(&results[1]).unwrap();
+
+        assert_eq!(json["published"], "1999-01-04T17:02:43Z");
+        assert_eq!(json["primary_category"], "hep-th");
+        assert_eq!(json["authors"][1], "Claudio Teitelboim");
+        assert_eq!(
+            json["author_affiliations"][1][1],
+            "Institute for Advanced Study"
+        );
+        assert_eq!(json["links"][0]["rel"], "related");
+        assert!(json["links"][0].get("@href").is_none());
+
+        let parsed: ArxivResult = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, results[1]);
+        assert_eq!(parsed.author_affiliations, results[1].author_affiliations);
+        assert_eq!(parsed.links, results[1].links);
+        assert_eq!(parsed.updated, results[1].updated);
+    }
+
+    #[test]
+    fn test_legacy_json() {
+        let mut json = serde_json::to_value(&fixture()[0]).unwrap();
+        json["updated"] = "+002024-02-23T18:35:15.000000000Z".into();
+        json.as_object_mut().unwrap().remove("author_affiliations");
+
+        let parsed: ArxivResult = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.updated, fixture()[0].updated);
+        assert!(parsed.author_affiliations.is_empty());
+        assert!(parsed.authors_detailed()[0].affiliations.is_empty());
+    }
+
+    #[test]
+    fn test_identity() {
+        let results = fixture();
+
+        let mut other = results[0].clone();
+        other.id = other.id.replace("http://", "https://");
+        other.title.clear();
+        assert_eq!(other, results[0]);
+
+        let mut next_version = results[0].clone();
+        next_version.id = next_version.id.replace("v1", "v2");
+        assert_ne!(next_version, results[0]);
+
+        let set = results
+            .iter()
+            .chain([&other, &next_version])
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(set.len(), 4);
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema() {
+        let schema = serde_json::to_value(ArxivResult::json_schema()).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+
+        assert!(properties.contains_key("primary_category"));
+        assert_eq!(properties["published"]["type"], "string");
+        assert!(schema["definitions"]["Link"]["properties"]
+            .as_object()
+            .unwrap()
+            .contains_key("content_type"));
+    }
 }
@@ -0,0 +1,310 @@
+use std::fmt::Display;
+use std::io::BufRead;
+use std::ops::Deref;
+
+use anyhow::{anyhow, bail};
+use quick_xml::events::Event;
+use quick_xml::name::QName;
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{ArxivResult, Entry, FieldError};
+use crate::stream::FeedReader;
+
+/// How to handle entries that cannot be turned into an [`ArxivResult`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Skip bad entries and report them as [`ParseDiagnostic`]s.
+    #[default]
+    Lenient,
+    /// Fail the whole response on the first bad entry.
+    Strict,
+}
+
+/// Why a single feed entry was skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParseDiagnostic {
+    /// Position of the entry in the feed, starting at 0.
+    pub entry_index: usize,
+    pub entry_id: Option<String>,
+    /// The offending field, when the problem could be narrowed down to one.
+    pub field: Option<String>,
+    pub reason: String,
+}
+
+impl Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "entry #{}", self.entry_index)?;
+        if let Some(id) = &self.entry_id {
+            write!(f, " ({})", id)?;
+        }
+        if let Some(field) = &self.field {
+            write!(f, " field `{}`", field)?;
+        }
+        write!(f, ": {}", self.reason)
+    }
+}
+
+/// The entries of a response that parsed, plus diagnostics for those that
+/// did not. Dereferences to the parsed results.
+#[derive(Debug, Clone, Default)]
+pub struct SearchResults {
+    pub results: Vec<ArxivResult>,
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl SearchResults {
+    pub fn into_results(self) -> Vec<ArxivResult> {
+        self.results
+    }
+
+    pub fn has_diagnostics(&self) -> bool {
+        !self.diagnostics.is_empty()
+    }
+
+    pub(crate) fn extend(&mut self, other: SearchResults) {
+        self.results.extend(other.results);
+        self.diagnostics.extend(other.diagnostics);
+    }
+}
+
+impl Deref for SearchResults {
+    type Target = Vec<ArxivResult>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.results
+    }
+}
+
+impl IntoIterator for SearchResults {
+    type Item = ArxivResult;
+    type IntoIter = std::vec::IntoIter<ArxivResult>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.results.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a SearchResults {
+    type Item = &'a ArxivResult;
+    type IntoIter = std::slice::Iter<'a, ArxivResult>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.results.iter()
+    }
+}
+
+/// Best-effort id lookup for entries that failed to deserialize.
+fn sniff_id(entry_xml: &str) -> Option<String> {
+    let start = entry_xml.find("<id>")? + "<id>".len();
+    let end = start + entry_xml[start..].find("</id>")?;
+    Some(entry_xml[start..end].trim().to_string())
+}
+
+fn parse_entry(index: usize, entry_xml: &str) -> Result<ArxivResult, Vec<ParseDiagnostic>> {
+    let entry = quick_xml::de::from_str::<Entry>(entry_xml).map_err(|e| {
+        vec![ParseDiagnostic {
+            entry_index: index,
+            entry_id: sniff_id(entry_xml),
+            field: None,
+            reason: e.to_string(),
+        }]
+    })?;
+    let entry_id = entry.id.clone();
+
+    ArxivResult::try_from_entry(entry).map_err(|errors| diagnostics_from(index, entry_id, errors))
+}
+
+pub(crate) fn diagnostics_from(
+    index: usize,
+    entry_id: Option<String>,
+    errors: Vec<FieldError>,
+) -> Vec<ParseDiagnostic> {
+    errors
+        .into_iter()
+        .map(|FieldError { field, reason }| ParseDiagnostic {
+            entry_index: index,
+            entry_id: entry_id.clone(),
+            field: Some(field.to_string()),
+            reason,
+        })
+        .collect()
+}
+
+/// Splits the feed into the raw text of its `<entry>` elements, so that one
+/// malformed entry cannot take the rest of the page down with it. Fails for
+/// a body without a `<feed>` element, such as an HTML error page.
+fn entry_slices(text: &str) -> anyhow::Result<Vec<&str>> {
+    let mut reader = Reader::from_str(text);
+    let mut slices = vec![];
+    let mut is_feed = false;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"feed" => {
+                is_feed = true;
+            }
+            Event::Start(e) if e.local_name().as_ref() == b"entry" => {
+                let name = e.name().as_ref().to_vec();
+                reader.read_to_end(QName(&name))?;
+                slices.push(&text[start..reader.buffer_position() as usize]);
+            }
+            Event::Empty(e) if e.local_name().as_ref() == b"entry" => {
+                slices.push(&text[start..reader.buffer_position() as usize]);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    if !is_feed {
+        bail!("Response is not an Atom feed");
+    }
+
+    Ok(slices)
+}
+
+pub(crate) fn parse_feed_str(text: &str, mode: ParseMode) -> anyhow::Result<SearchResults> {
+    let mut parsed = SearchResults::default();
+
+    for (index, entry_xml) in entry_slices(text)?.into_iter().enumerate() {
+        match parse_entry(index, entry_xml) {
+            Ok(result) => parsed.results.push(result),
+            Err(diagnostics) => match mode {
+                ParseMode::Lenient => parsed.diagnostics.extend(diagnostics),
+                ParseMode::Strict => {
+                    return Err(anyhow!("Failed to parse feed: {}", diagnostics[0]));
+                }
+            },
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Parses a stored arXiv Atom response without a client, leniently.
+pub fn parse_feed(xml: &str) -> anyhow::Result<SearchResults> {
+    parse_feed_str(xml, ParseMode::Lenient)
+}
+
+pub fn parse_feed_with_mode(xml: &str, mode: ParseMode) -> anyhow::Result<SearchResults> {
+    parse_feed_str(xml, mode)
+}
+
+/// Parses a stored response with the streaming [`FeedReader`], so the
+/// document is never held in memory as a whole.
+pub fn parse_feed_reader(reader: impl BufRead) -> anyhow::Result<SearchResults> {
+    let mut reader = FeedReader::new(reader);
+    let results = reader.by_ref().collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(SearchResults {
+        results,
+        diagnostics: reader.into_diagnostics(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::FEED;
+
+    fn broken_feed() -> String {
+        FEED.replacen("<updated>2024-02-23T18:35:15Z</updated>", "<updated>yesterday</updated>", 1)
+            .replacen(
+                r#"<arxiv:primary_category xmlns:arxiv="http://arxiv.org/schemas/atom" term="hep-th" scheme="http://arxiv.org/schemas/atom"/>"#,
+                "",
+                1,
+            )
+    }
+
+    #[test]
+    fn test_parse_feed() {
+        let parsed = parse_feed_str(FEED, ParseMode::Lenient).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert!(!parsed.has_diagnostics());
+    }
+
+    #[test]
+    fn test_lenient() {
+        let parsed = parse_feed_str(&broken_feed(), ParseMode::Lenient).unwrap();
+
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].id, "http://arxiv.org/abs/2101.00027v3");
+        assert_eq!(
+            parsed.diagnostics,
+            vec![
+                ParseDiagnostic {
+                    entry_index: 0,
+                    entry_id: Some("http://arxiv.org/abs/2402.16893v1".to_string()),
+                    field: Some("updated".to_string()),
+                    reason:
+                        "invalid timestamp \"yesterday\": the 'year' component could not be parsed"
+                            .to_string(),
+                },
+                ParseDiagnostic {
+                    entry_index: 1,
+                    entry_id: Some("http://arxiv.org/abs/hep-th/9901001v2".to_string()),
+                    field: Some("primary_category".to_string()),
+                    reason: "missing".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_structurally_broken_entry() {
+        let feed = FEED.replacen("<name>Shenglai Zeng</name>", "", 1);
+        let parsed = parse_feed_str(&feed, ParseMode::Lenient).unwrap();
+
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed.diagnostics.len(), 1);
+        assert_eq!(
+            parsed.diagnostics[0].entry_id.as_deref(),
+            Some("http://arxiv.org/abs/2402.16893v1")
+        );
+        assert_eq!(parsed.diagnostics[0].field, None);
+    }
+
+    #[test]
+    fn test_strict() {
+        let err = parse_feed_str(&broken_feed(), ParseMode::Strict).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "Failed to parse feed: entry #0 (http://arxiv.org/abs/2402.16893v1) field `updated`"
+        ));
+    }
+
+    #[test]
+    fn test_not_a_feed() {
+        let html = "<!DOCTYPE html><html><body><p>Service Unavailable</p></body></html>";
+        for text in ["Rate exceeded.", html, ""] {
+            for mode in [ParseMode::Strict, ParseMode::Lenient] {
+                let err = parse_feed_str(text, mode).unwrap_err();
+                assert_eq!(
+                    err.to_string(),
+                    "Response is not an Atom feed",
+                    "{:?}",
+                    text
+                );
+            }
+            assert!(parse_feed_reader(text.as_bytes()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_feed_reader() {
+        let parsed = parse_feed_reader(std::io::Cursor::new(FEED)).unwrap();
+        assert_eq!(parsed.len(), 3);
+
+        let parsed = parse_feed_reader(std::io::BufReader::new(
+            std::fs::File::open("testdata/feed.xml").unwrap(),
+        ))
+        .unwrap();
+        assert_eq!(parsed[1].id, "http://arxiv.org/abs/hep-th/9901001v2");
+    }
+
+    #[test]
+    fn test_parse_feed_with_mode() {
+        assert!(parse_feed(&broken_feed()).is_ok());
+        assert!(parse_feed_with_mode(&broken_feed(), ParseMode::Strict).is_err());
+    }
+}
@@ -82,6 +82,10 @@ impl<S> ArxivQuery<S> {
         self
     }
 
+    pub(crate) fn max_results(&self) -> usize {
+        self.max_results
+    }
+
     pub fn next_page_query(mut self) -> Self {
         self.start += self.max_results;
         self
@@ -0,0 +1,259 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{anyhow, bail, Context as _};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+
+use crate::parse::{parse_feed_str, ParseMode, SearchResults};
+
+/// An API response exactly as arXiv sent it.
+#[derive(Debug, Clone)]
+pub struct RawResponse {
+    pub url: String,
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl RawResponse {
+    pub fn text(&self) -> anyhow::Result<&str> {
+        std::str::from_utf8(&self.body).context("Response body is not valid UTF-8")
+    }
+
+    /// Fails for a non-2xx status, with the start of the body, which is
+    /// where arXiv explains errors such as `Rate exceeded.`.
+    pub fn error_for_status(&self) -> anyhow::Result<()> {
+        if self.status.is_success() {
+            return Ok(());
+        }
+        let body = String::from_utf8_lossy(&self.body);
+        let body = body.trim().chars().take(200).collect::<String>();
+        bail!("arXiv responded with {}: {}", self.status, body)
+    }
+
+    /// Whether the request may succeed when sent again later.
+    pub(crate) fn is_transient_error(&self) -> bool {
+        self.status.is_server_error() || self.status == StatusCode::TOO_MANY_REQUESTS
+    }
+
+    pub fn parse(&self, mode: ParseMode) -> anyhow::Result<SearchResults> {
+        self.error_for_status()?;
+        parse_feed_str(self.text()?, mode)
+    }
+}
+
+/// Called with every response the client receives, before it is parsed.
+/// Closures taking a `&RawResponse` implement this trait.
+pub trait ResponseRecorder: Send + Sync {
+    fn record(&self, response: &RawResponse) -> anyhow::Result<()>;
+
+    /// Called when [`ResponseRecorder::record`] fails, as recording errors
+    /// do not fail the search. Does nothing by default.
+    fn record_failed(&self, _response: &RawResponse, _error: anyhow::Error) {}
+}
+
+impl<F> ResponseRecorder for F
+where
+    F: Fn(&RawResponse) -> anyhow::Result<()> + Send + Sync,
+{
+    fn record(&self, response: &RawResponse) -> anyhow::Result<()> {
+        self(response)
+    }
+}
+
+/// Persists each response as a pair of files: `NNNNNN.xml` with the body and
+/// `NNNNNN.http` with the request URL, status and headers. Numbering resumes
+/// after the highest-numbered record already in the directory.
+#[derive(Debug)]
+pub struct DirectoryRecorder {
+    dir: PathBuf,
+    next: AtomicUsize,
+}
+
+fn record_paths(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut paths = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "http"));
+    paths.sort();
+
+    Ok(paths)
+}
+
+impl DirectoryRecorder {
+    pub fn new(dir: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+        let next = record_paths(&dir)?
+            .iter()
+            .filter_map(|path| path.file_stem()?.to_str()?.parse::<usize>().ok())
+            .max()
+            .map_or(0, |last| last + 1);
+
+        Ok(Self {
+            dir,
+            next: AtomicUsize::new(next),
+        })
+    }
+
+    /// Loads every recorded response in `dir`, oldest first.
+    pub fn replay(dir: impl AsRef<Path>) -> anyhow::Result<Vec<RawResponse>> {
+        record_paths(dir.as_ref())?
+            .into_iter()
+            .map(|path| read_record(&path))
+            .collect()
+    }
+}
+
+impl ResponseRecorder for DirectoryRecorder {
+    fn record(&self, response: &RawResponse) -> anyhow::Result<()> {
+        let n = self.next.fetch_add(1, Ordering::SeqCst);
+        let stem = self.dir.join(format!("{:06}", n));
+
+        let mut meta = format!("GET {}\n{}\n", response.url, response.status.as_u16());
+        for (name, value) in &response.headers {
+            meta.push_str(&format!(
+                "{}: {}\n",
+                name,
+                String::from_utf8_lossy(value.as_bytes())
+            ));
+        }
+
+        fs::write(stem.with_extension("xml"), &response.body)
+            .with_context(|| format!("Failed to write {}.xml", stem.display()))?;
+        fs::write(stem.with_extension("http"), meta)
+            .with_context(|| format!("Failed to write {}.http", stem.display()))?;
+
+        Ok(())
+    }
+}
+
+fn read_record(meta_path: &Path) -> anyhow::Result<RawResponse> {
+    let meta = fs::read_to_string(meta_path)
+        .with_context(|| format!("Failed to read {}", meta_path.display()))?;
+    let mut lines = meta.lines();
+    let invalid = || anyhow!("Invalid response record: {}", meta_path.display());
+
+    let url = lines
+        .next()
+        .and_then(|line| line.strip_prefix("GET "))
+        .ok_or_else(invalid)?
+        .to_string();
+    let status = lines
+        .next()
+        .and_then(|line| line.parse::<u16>().ok())
+        .and_then(|status| StatusCode::from_u16(status).ok())
+        .ok_or_else(invalid)?;
+
+    let mut headers = HeaderMap::new();
+    for line in lines {
+        let (name, value) = line.split_once(": ").ok_or_else(invalid)?;
+        headers.append(
+            HeaderName::from_bytes(name.as_bytes())?,
+            HeaderValue::from_str(value)?,
+        );
+    }
+
+    let body_path = meta_path.with_extension("xml");
+    let body =
+        fs::read(&body_path).with_context(|| format!("Failed to read {}", body_path.display()))?;
+
+    Ok(RawResponse {
+        url,
+        status,
+        headers,
+        body,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::FEED;
+
+    fn response() -> RawResponse {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("application/atom+xml; charset=UTF-8"),
+        );
+
+        RawResponse {
+            url: "http://export.arxiv.org/api/query?search_query=all%3ARAG".to_string(),
+            status: StatusCode::OK,
+            headers,
+            body: FEED.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let results = response().parse(ParseMode::Strict).unwrap();
+        assert_eq!(results.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_error_status() {
+        let mut response = response();
+        response.status = StatusCode::SERVICE_UNAVAILABLE;
+        response.body = b"Rate exceeded.".to_vec();
+        assert!(response.is_transient_error());
+
+        let error = response.parse(ParseMode::Lenient).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "arXiv responded with 503 Service Unavailable: Rate exceeded."
+        );
+    }
+
+    #[test]
+    fn test_directory_recorder_round_trip() {
+        let dir =
+            std::env::temp_dir().join(format!("arxiv-api-rs-recorder-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let recorder = DirectoryRecorder::new(&dir).unwrap();
+        recorder.record(&response()).unwrap();
+        recorder.record(&response()).unwrap();
+        // A new recorder must not overwrite earlier records.
+        DirectoryRecorder::new(&dir)
+            .unwrap()
+            .record(&response())
+            .unwrap();
+
+        // Nor after one of them was deleted.
+        fs::remove_file(dir.join("000000.http")).unwrap();
+        fs::remove_file(dir.join("000000.xml")).unwrap();
+        DirectoryRecorder::new(&dir)
+            .unwrap()
+            .record(&response())
+            .unwrap();
+        assert!(dir.join("000003.http").exists());
+
+        let replayed = DirectoryRecorder::replay(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(replayed.len(), 3);
+        let original = response();
+        assert_eq!(replayed[2].url, original.url);
+        assert_eq!(replayed[2].status, original.status);
+        assert_eq!(replayed[2].headers, original.headers);
+        assert_eq!(replayed[2].body, original.body);
+    }
+
+    #[test]
+    fn test_closure_recorder() {
+        let recorded = std::sync::Mutex::new(vec![]);
+        let recorder = |response: &RawResponse| {
+            recorded.lock().unwrap().push(response.url.clone());
+            Ok(())
+        };
+
+        recorder.record(&response()).unwrap();
+        assert_eq!(recorded.lock().unwrap().len(), 1);
+    }
+}
@@ -1,8 +1,16 @@
 use std::fmt::{Debug, Display};
 
+use serde::{Deserialize, Serialize};
 use time::format_description::well_known::{Iso8601, Rfc2822, Rfc3339};
-use time::macros::format_description;
-use time::OffsetDateTime;
+use time::macros::{format_description, time};
+use time::{Date, Duration, OffsetDateTime, UtcOffset};
+
+use crate::category::{validate_category_term, Category};
+use crate::clock::Clock;
+
+mod parser;
+
+pub use parser::QueryParseError;
 
 fn remove_outside_brackets(s: &str) -> String {
     if s.starts_with('(') && s.ends_with(')') {
@@ -16,7 +24,8 @@ pub trait ISearchQuery {
     fn to_query_string(&self) -> String;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RangeField {
     LastUpdatedDate,
     SubmittedDate,
@@ -31,7 +40,8 @@ impl AsRef<str> for RangeField {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SearchField {
     Title,
     Author,
@@ -60,62 +70,167 @@ impl AsRef<str> for SearchField {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Stands in for a missing lower bound; arXiv has no papers before 1991.
+const OPEN_START: &str = "199101010000";
+/// Stands in for a missing upper bound.
+const OPEN_END: &str = "999912312359";
+
+/// A date range; either bound may be left open.
+///
+/// The constructors keep bounds to the minute in UTC, which is all a query
+/// can express, and take the dates that stand in for open bounds
+/// (1991-01-01 00:00 and 9999-12-31 23:59) as open, so that a range equals
+/// the range parsed back from its query.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "SearchRangeFields")]
 pub struct SearchRange {
     field: RangeField,
-    start: OffsetDateTime,
-    end: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    start: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    end: Option<OffsetDateTime>,
+}
+
+/// A `SearchRange` as serialized, before its bounds are normalized.
+#[derive(Deserialize)]
+struct SearchRangeFields {
+    field: RangeField,
+    #[serde(with = "time::serde::rfc3339::option")]
+    start: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    end: Option<OffsetDateTime>,
+}
+
+impl From<SearchRangeFields> for SearchRange {
+    fn from(fields: SearchRangeFields) -> Self {
+        Self {
+            field: fields.field,
+            start: fields
+                .start
+                .and_then(|start| range_bound(start, OPEN_START)),
+            end: fields.end.and_then(|end| range_bound(end, OPEN_END)),
+        }
+    }
+}
+
+/// A bound as a query expresses it: cut to the minute in UTC, or `None` for
+/// the sentinel of an open bound.
+fn range_bound(timestamp: OffsetDateTime, open: &str) -> Option<OffsetDateTime> {
+    let timestamp = timestamp
+        .to_offset(UtcOffset::UTC)
+        .replace_second(0)
+        .and_then(|timestamp| timestamp.replace_nanosecond(0))
+        .expect("0 is a valid second and nanosecond");
+    (format_range_bound(timestamp) != open).then_some(timestamp)
 }
 
 impl SearchRange {
     pub fn new(field: RangeField, start: OffsetDateTime, end: OffsetDateTime) -> Self {
-        Self { field, start, end }
+        Self {
+            field,
+            start: range_bound(start, OPEN_START),
+            end: range_bound(end, OPEN_END),
+        }
+    }
+
+    /// Everything from `start` on.
+    pub fn since(field: RangeField, start: OffsetDateTime) -> Self {
+        Self {
+            field,
+            start: range_bound(start, OPEN_START),
+            end: None,
+        }
+    }
+
+    /// Everything up to `end`.
+    pub fn until(field: RangeField, end: OffsetDateTime) -> Self {
+        Self {
+            field,
+            start: None,
+            end: range_bound(end, OPEN_END),
+        }
+    }
+
+    /// The `days` days up to the clock's current time, or everything up to
+    /// it if that reaches back further than dates can go.
+    pub fn last_days(field: RangeField, days: u32, clock: impl Clock) -> Self {
+        let now = clock.now();
+        Self {
+            field,
+            start: now
+                .checked_sub(Duration::days(days.into()))
+                .and_then(|start| range_bound(start, OPEN_START)),
+            end: range_bound(now, OPEN_END),
+        }
+    }
+
+    /// Everything since the start of the clock's current month in UTC.
+    pub fn this_month(field: RangeField, clock: impl Clock) -> Self {
+        let today = clock.now().to_offset(UtcOffset::UTC).date();
+        let first = today.replace_day(1).expect("every month has a first day");
+        Self::since(field, first.midnight().assume_utc())
     }
 
     pub fn try_from_iso_8601(field: RangeField, start: &str, end: &str) -> anyhow::Result<Self> {
-        Ok(Self {
+        Ok(Self::new(
             field,
-            start: OffsetDateTime::parse(start, &Iso8601::DEFAULT)?,
-            end: OffsetDateTime::parse(end, &Iso8601::DEFAULT)?,
-        })
+            OffsetDateTime::parse(start, &Iso8601::DEFAULT)?,
+            OffsetDateTime::parse(end, &Iso8601::DEFAULT)?,
+        ))
     }
 
     pub fn try_from_rfc_3339(field: RangeField, start: &str, end: &str) -> anyhow::Result<Self> {
-        Ok(Self {
+        Ok(Self::new(
             field,
-            start: OffsetDateTime::parse(start, &Rfc3339)?,
-            end: OffsetDateTime::parse(end, &Rfc3339)?,
-        })
+            OffsetDateTime::parse(start, &Rfc3339)?,
+            OffsetDateTime::parse(end, &Rfc3339)?,
+        ))
     }
 
     pub fn try_from_rfc_2822(field: RangeField, start: &str, end: &str) -> anyhow::Result<Self> {
-        Ok(Self {
+        Ok(Self::new(
             field,
-            start: OffsetDateTime::parse(start, &Rfc2822)?,
-            end: OffsetDateTime::parse(end, &Rfc2822)?,
-        })
+            OffsetDateTime::parse(start, &Rfc2822)?,
+            OffsetDateTime::parse(end, &Rfc2822)?,
+        ))
     }
 
     pub fn try_from_date(field: RangeField, start: &str, end: &str) -> anyhow::Result<Self> {
-        Ok(Self {
+        Ok(Self::from_dates(
+            field,
+            Date::parse(start, format_description!("[year]-[month]-[day]"))?,
+            Date::parse(end, format_description!("[year]-[month]-[day]"))?,
+        ))
+    }
+
+    /// A range covering whole days in UTC, from the start of `start` to the
+    /// last minute of `end`.
+    pub fn from_dates(field: RangeField, start: Date, end: Date) -> Self {
+        Self::new(
             field,
-            start: OffsetDateTime::parse(start, format_description!("[year]-[month]-[day]"))?,
-            end: OffsetDateTime::parse(end, format_description!("[year]-[month]-[day]"))?,
-        })
+            start.midnight().assume_utc(),
+            end.with_time(time!(23:59)).assume_utc(),
+        )
     }
 }
 
+/// Formats a timestamp as arXiv expects in ranges: `YYYYMMDDHHMM` in GMT.
+fn format_range_bound(timestamp: OffsetDateTime) -> String {
+    timestamp
+        .to_offset(UtcOffset::UTC)
+        .format(format_description!("[year][month][day][hour][minute]"))
+        .expect("invalid range offset datetime")
+}
+
 impl ISearchQuery for SearchRange {
     fn to_query_string(&self) -> String {
         format!(
             "{}:[{} TO {}]",
             self.field.as_ref(),
             self.start
-                .format(&Iso8601::DEFAULT)
-                .expect("invalid start offset datetime"), // 1970-01-01T00:00:00Z
+                .map_or_else(|| OPEN_START.to_string(), format_range_bound),
             self.end
-                .format(&Iso8601::DEFAULT)
-                .expect("invalid end offset datetime"), // 1970-01-01T00:16:40Z
+                .map_or_else(|| OPEN_END.to_string(), format_range_bound),
         )
     }
 }
@@ -126,24 +241,90 @@ impl Display for SearchRange {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Characters arXiv's query syntax gives a meaning of its own.
+fn is_special(c: char) -> bool {
+    c.is_whitespace() || matches!(c, ':' | '(' | ')' | '"' | '\\')
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SearchTerm {
     field: SearchField,
     term: String,
+    quoted: bool,
 }
 
 impl SearchTerm {
+    /// A term that is quoted as a phrase if it contains whitespace or
+    /// characters of the query syntax, e.g. `au:"John Doe"`.
     pub fn new<S: ToString>(field: SearchField, term: S) -> Self {
+        let term = term.to_string();
+        let quoted = term.contains(is_special);
+
+        Self {
+            field,
+            term,
+            quoted,
+        }
+    }
+
+    /// A term that is always quoted, so its words must appear next to each
+    /// other and in order.
+    pub fn phrase<S: ToString>(field: SearchField, term: S) -> Self {
         Self {
             field,
             term: term.to_string(),
+            quoted: true,
+        }
+    }
+
+    /// A term that is never quoted; whitespace and special characters are
+    /// escaped with a backslash instead.
+    pub fn word<S: ToString>(field: SearchField, term: S) -> Self {
+        Self {
+            field,
+            term: term.to_string(),
+            quoted: false,
+        }
+    }
+
+    /// Like [`SearchTerm::new`], but rejects `cat:` terms that are not part of
+    /// the arXiv taxonomy instead of silently matching nothing.
+    pub fn try_new<S: ToString>(field: SearchField, term: S) -> anyhow::Result<Self> {
+        let term = term.to_string();
+        if let SearchField::SubjectCategory = field {
+            validate_category_term(&term)?;
         }
+
+        Ok(Self::new(field, term))
+    }
+
+    pub fn category(category: &Category) -> Self {
+        Self::new(SearchField::SubjectCategory, category)
     }
 }
 
 impl ISearchQuery for SearchTerm {
     fn to_query_string(&self) -> String {
-        format!("{}:{}", self.field.as_ref(), self.term)
+        let mut term = String::with_capacity(self.term.len() + 2);
+        if self.quoted {
+            term.push('"');
+        }
+        for c in self.term.chars() {
+            let escape = if self.quoted {
+                matches!(c, '"' | '\\')
+            } else {
+                is_special(c)
+            };
+            if escape {
+                term.push('\\');
+            }
+            term.push(c);
+        }
+        if self.quoted {
+            term.push('"');
+        }
+
+        format!("{}:{}", self.field.as_ref(), term)
     }
 }
 
@@ -157,56 +338,81 @@ fn bracket_format(query: &str) -> String {
     format!("({})", query)
 }
 
-pub enum SearchPredicate<'a> {
-    And(Vec<Box<dyn ISearchQuery + 'a>>),
-    Or(Vec<Box<dyn ISearchQuery + 'a>>),
-    AndNot(Box<dyn ISearchQuery + 'a>, Box<dyn ISearchQuery + 'a>),
-    Bracket(Box<dyn ISearchQuery + 'a>),
+fn join_query_strings(predicates: &[SearchPredicate], separator: &str) -> String {
+    predicates
+        .iter()
+        .map(|predicate| predicate.to_query_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// A search query as a tree of terms, ranges and boolean operators.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchPredicate {
+    Term(SearchTerm),
+    Range(SearchRange),
+    And(Vec<SearchPredicate>),
+    Or(Vec<SearchPredicate>),
+    AndNot(Box<SearchPredicate>, Box<SearchPredicate>),
+    /// Extra brackets around a predicate.
+    Group(Box<SearchPredicate>),
 }
 
-impl<'a> SearchPredicate<'a> {
-    pub fn and(lhs: impl ISearchQuery + 'a, rhs: impl ISearchQuery + 'a) -> Self {
-        SearchPredicate::And(vec![Box::new(lhs), Box::new(rhs)])
+impl SearchPredicate {
+    pub fn and(lhs: impl Into<SearchPredicate>, rhs: impl Into<SearchPredicate>) -> Self {
+        SearchPredicate::And(vec![lhs.into(), rhs.into()])
+    }
+
+    pub fn and_all<P: Into<SearchPredicate>>(predicates: impl IntoIterator<Item = P>) -> Self {
+        SearchPredicate::And(predicates.into_iter().map(Into::into).collect())
     }
 
-    pub fn and_all(predicates: Vec<Box<dyn ISearchQuery + 'a>>) -> Self {
-        SearchPredicate::And(predicates)
+    pub fn or(lhs: impl Into<SearchPredicate>, rhs: impl Into<SearchPredicate>) -> Self {
+        SearchPredicate::Or(vec![lhs.into(), rhs.into()])
     }
 
-    pub fn or(lhs: impl ISearchQuery + 'a, rhs: impl ISearchQuery + 'a) -> Self {
-        SearchPredicate::Or(vec![Box::new(lhs), Box::new(rhs)])
+    pub fn or_all<P: Into<SearchPredicate>>(predicates: impl IntoIterator<Item = P>) -> Self {
+        SearchPredicate::Or(predicates.into_iter().map(Into::into).collect())
     }
 
-    pub fn or_all(predicates: Vec<Box<dyn ISearchQuery + 'a>>) -> Self {
-        SearchPredicate::Or(predicates)
+    pub fn and_not(lhs: impl Into<SearchPredicate>, rhs: impl Into<SearchPredicate>) -> Self {
+        SearchPredicate::AndNot(Box::new(lhs.into()), Box::new(rhs.into()))
     }
 
-    pub fn and_not(lhs: impl ISearchQuery + 'a, rhs: impl ISearchQuery + 'a) -> Self {
-        SearchPredicate::AndNot(Box::new(lhs), Box::new(rhs))
+    pub fn group(predicate: impl Into<SearchPredicate>) -> Self {
+        SearchPredicate::Group(Box::new(predicate.into()))
     }
 
-    pub fn bracket(predicate: impl ISearchQuery + 'a) -> Self {
-        SearchPredicate::Bracket(Box::new(predicate))
+    #[deprecated(note = "use `SearchPredicate::group`")]
+    pub fn bracket(predicate: impl Into<SearchPredicate>) -> Self {
+        Self::group(predicate)
     }
 }
 
-impl<'a> ISearchQuery for SearchPredicate<'a> {
+impl From<SearchTerm> for SearchPredicate {
+    fn from(term: SearchTerm) -> Self {
+        SearchPredicate::Term(term)
+    }
+}
+
+impl From<SearchRange> for SearchPredicate {
+    fn from(range: SearchRange) -> Self {
+        SearchPredicate::Range(range)
+    }
+}
+
+impl ISearchQuery for SearchPredicate {
     fn to_query_string(&self) -> String {
         match self {
-            SearchPredicate::And(predicates) => bracket_format(
-                &predicates
-                    .iter()
-                    .map(|predicate| predicate.to_query_string())
-                    .collect::<Vec<_>>()
-                    .join(" AND "),
-            ),
-            SearchPredicate::Or(predicates) => bracket_format(
-                &predicates
-                    .iter()
-                    .map(|predicate| predicate.to_query_string())
-                    .collect::<Vec<_>>()
-                    .join(" OR "),
-            ),
+            SearchPredicate::Term(term) => term.to_query_string(),
+            SearchPredicate::Range(range) => range.to_query_string(),
+            SearchPredicate::And(predicates) => {
+                bracket_format(&join_query_strings(predicates, " AND "))
+            }
+            SearchPredicate::Or(predicates) => {
+                bracket_format(&join_query_strings(predicates, " OR "))
+            }
             SearchPredicate::AndNot(lhs, rhs) => {
                 format!(
                     "({} ANDNOT {})",
@@ -214,14 +420,12 @@ impl<'a> ISearchQuery for SearchPredicate<'a> {
                     rhs.to_query_string()
                 )
             }
-            SearchPredicate::Bracket(inner) => {
-                format!("({})", inner.to_query_string())
-            }
+            SearchPredicate::Group(inner) => bracket_format(&inner.to_query_string()),
         }
     }
 }
 
-impl Display for SearchPredicate<'_> {
+impl Display for SearchPredicate {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", remove_outside_brackets(&self.to_query_string()))
     }
@@ -230,6 +434,8 @@ impl Display for SearchPredicate<'_> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::clock::{FixedClock, SystemClock};
+    use time::macros::datetime;
 
     #[test]
     fn test_search_term() {
@@ -238,6 +444,33 @@ mod test {
         assert_eq!(term.to_string(), "ti:RAG");
     }
 
+    #[test]
+    fn test_phrase_search_term() {
+        let term = SearchTerm::new(SearchField::Author, "John Doe");
+        assert_eq!(term.to_string(), "au:\"John Doe\"");
+
+        let term = SearchTerm::phrase(SearchField::Title, "RAG");
+        assert_eq!(term.to_string(), "ti:\"RAG\"");
+
+        let term = SearchTerm::new(SearchField::Title, "say \"hello\" (again)");
+        assert_eq!(term.to_string(), "ti:\"say \\\"hello\\\" (again)\"");
+
+        let term = SearchTerm::word(SearchField::Comment, "note:v2 (draft)");
+        assert_eq!(term.to_string(), "co:note\\:v2\\ \\(draft\\)");
+    }
+
+    #[test]
+    fn test_category_search_term() {
+        let term = SearchTerm::try_new(SearchField::SubjectCategory, "cs.CL").unwrap();
+        assert_eq!(term.to_string(), "cat:cs.CL");
+
+        assert!(SearchTerm::try_new(SearchField::SubjectCategory, "cs.Al").is_err());
+        assert!(SearchTerm::try_new(SearchField::Title, "cs.Al").is_ok());
+
+        let category = Category::new("math.IT");
+        assert_eq!(SearchTerm::category(&category).to_string(), "cat:math.IT");
+    }
+
     #[test]
     fn test_search_range() {
         let start = OffsetDateTime::from_unix_timestamp(0).unwrap();
@@ -245,11 +478,93 @@ mod test {
         let range = SearchRange::new(RangeField::LastUpdatedDate, start, end);
         assert_eq!(
             range.to_query_string(),
-            "lastUpdatedDate:[1970-01-01T00:00:00.000000000Z TO 1970-01-01T00:16:40.000000000Z]"
+            "lastUpdatedDate:[197001010000 TO 197001010016]"
         );
         assert_eq!(
             range.to_string(),
-            "lastUpdatedDate:[1970-01-01T00:00:00.000000000Z TO 1970-01-01T00:16:40.000000000Z]"
+            "lastUpdatedDate:[197001010000 TO 197001010016]"
+        );
+        assert_eq!(
+            range,
+            SearchRange::new(
+                RangeField::LastUpdatedDate,
+                start,
+                OffsetDateTime::from_unix_timestamp(960).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_search_range_offset() {
+        let start = OffsetDateTime::parse("2024-02-23T09:30:00+09:00", &Rfc3339).unwrap();
+        let end = OffsetDateTime::parse("2024-02-24T18:45:00-05:00", &Rfc3339).unwrap();
+        let range = SearchRange::new(RangeField::SubmittedDate, start, end);
+        assert_eq!(
+            range.to_string(),
+            "submittedDate:[202402230030 TO 202402242345]"
+        );
+    }
+
+    #[test]
+    fn test_open_search_range() {
+        let start = OffsetDateTime::parse("2020-01-01T00:00:00Z", &Rfc3339).unwrap();
+        assert_eq!(
+            SearchRange::since(RangeField::SubmittedDate, start).to_string(),
+            "submittedDate:[202001010000 TO 999912312359]"
+        );
+        assert_eq!(
+            SearchRange::until(RangeField::LastUpdatedDate, start).to_string(),
+            "lastUpdatedDate:[199101010000 TO 202001010000]"
+        );
+
+        let first = OffsetDateTime::parse("1991-01-01T00:00:00Z", &Rfc3339).unwrap();
+        let last = OffsetDateTime::parse("9999-12-31T23:59:30Z", &Rfc3339).unwrap();
+        let range = SearchRange::new(RangeField::SubmittedDate, first, last);
+        assert_eq!(range.start, None);
+        assert_eq!(range.end, None);
+    }
+
+    #[test]
+    fn test_relative_search_range() {
+        let clock =
+            FixedClock(OffsetDateTime::parse("2024-03-15T12:30:00+09:00", &Rfc3339).unwrap());
+        assert_eq!(
+            SearchRange::last_days(RangeField::SubmittedDate, 7, clock).to_string(),
+            "submittedDate:[202403080330 TO 202403150330]"
+        );
+        assert_eq!(
+            SearchRange::last_days(RangeField::SubmittedDate, u32::MAX, clock).to_string(),
+            "submittedDate:[199101010000 TO 202403150330]"
+        );
+        assert_eq!(
+            SearchRange::this_month(RangeField::SubmittedDate, clock).to_string(),
+            "submittedDate:[202403010000 TO 999912312359]"
+        );
+        assert!(
+            SearchRange::last_days(RangeField::SubmittedDate, 1, SystemClock)
+                .to_string()
+                .starts_with("submittedDate:[20")
+        );
+    }
+
+    #[test]
+    fn test_search_range_dates() {
+        let range = SearchRange::from_dates(
+            RangeField::SubmittedDate,
+            Date::from_calendar_date(2024, time::Month::January, 1).unwrap(),
+            Date::from_calendar_date(2024, time::Month::January, 31).unwrap(),
+        );
+        assert_eq!(
+            range.to_string(),
+            "submittedDate:[202401010000 TO 202401312359]"
+        );
+
+        let range =
+            SearchRange::try_from_date(RangeField::SubmittedDate, "2024-01-01", "2024-01-31")
+                .unwrap();
+        assert_eq!(
+            range.to_string(),
+            "submittedDate:[202401010000 TO 202401312359]"
         );
     }
 
@@ -258,8 +573,8 @@ mod test {
         let term1 = SearchTerm::new(SearchField::Title, "RAG");
         let term2 = SearchTerm::new(SearchField::Author, "John Doe");
         let predicate = SearchPredicate::and(term1, term2);
-        assert_eq!(predicate.to_query_string(), "(ti:RAG AND au:John Doe)");
-        assert_eq!(predicate.to_string(), "ti:RAG AND au:John Doe");
+        assert_eq!(predicate.to_query_string(), "(ti:RAG AND au:\"John Doe\")");
+        assert_eq!(predicate.to_string(), "ti:RAG AND au:\"John Doe\"");
     }
 
     #[test]
@@ -271,11 +586,56 @@ mod test {
         let or_predicate = SearchPredicate::or(and_predicate, term3);
         assert_eq!(
             or_predicate.to_query_string(),
-            "((ti:RAG AND au:John Doe) OR abs:Lorem Ipsum)"
+            "((ti:RAG AND au:\"John Doe\") OR abs:\"Lorem Ipsum\")"
         );
         assert_eq!(
             or_predicate.to_string(),
-            "(ti:RAG AND au:John Doe) OR abs:Lorem Ipsum"
+            "(ti:RAG AND au:\"John Doe\") OR abs:\"Lorem Ipsum\""
         );
     }
+
+    #[test]
+    fn test_predicate_serde() {
+        let predicate = SearchPredicate::and_not(
+            SearchPredicate::or_all([
+                SearchTerm::new(SearchField::Title, "RAG"),
+                SearchTerm::phrase(SearchField::Author, "John Doe"),
+            ]),
+            SearchRange::since(
+                RangeField::SubmittedDate,
+                OffsetDateTime::from_unix_timestamp(0).unwrap(),
+            ),
+        );
+
+        let json = serde_json::to_string(&predicate).unwrap();
+        let deserialized: SearchPredicate = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, predicate);
+        assert_eq!(deserialized.to_string(), predicate.clone().to_string());
+
+        let json = serde_json::to_value(SearchPredicate::group(SearchTerm::new(
+            SearchField::JournalReference,
+            "PRL",
+        )))
+        .unwrap();
+        assert_eq!(json["group"]["term"]["field"], "journal_reference");
+    }
+
+    #[test]
+    fn test_range_serde() {
+        let range: SearchRange = serde_json::from_str(
+            r#"{"field":"submitted_date","start":"2024-01-01T09:30:45.5+09:00","end":"9999-12-31T23:59:59Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            range,
+            SearchRange::since(RangeField::SubmittedDate, datetime!(2024-01-01 00:30 UTC))
+        );
+        assert_eq!(
+            range.to_string(),
+            "submittedDate:[202401010030 TO 999912312359]"
+        );
+
+        let json = serde_json::to_string(&range).unwrap();
+        assert_eq!(serde_json::from_str::<SearchRange>(&json).unwrap(), range);
+    }
 }
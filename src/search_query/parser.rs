@@ -0,0 +1,549 @@
+use std::fmt::Display;
+
+use time::macros::format_description;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+use super::{
+    RangeField, SearchField, SearchPredicate, SearchRange, SearchTerm, OPEN_END, OPEN_START,
+};
+
+/// A `search_query` string that could not be parsed. `position` is the byte
+/// offset in the input where the problem was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryParseError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for QueryParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for QueryParseError {}
+
+fn search_field(name: &str) -> Option<SearchField> {
+    let field = match name {
+        "ti" => SearchField::Title,
+        "au" => SearchField::Author,
+        "abs" => SearchField::Abstract,
+        "co" => SearchField::Comment,
+        "jr" => SearchField::JournalReference,
+        "cat" => SearchField::SubjectCategory,
+        "rn" => SearchField::ReportNumber,
+        "doi" => SearchField::Doi,
+        "all" => SearchField::All,
+        _ => return None,
+    };
+    Some(field)
+}
+
+fn range_field(name: &str) -> Option<RangeField> {
+    match name {
+        "lastUpdatedDate" => Some(RangeField::LastUpdatedDate),
+        "submittedDate" => Some(RangeField::SubmittedDate),
+        _ => None,
+    }
+}
+
+/// How deeply brackets may nest, so that untrusted input cannot exhaust the
+/// stack.
+const MAX_DEPTH: usize = 256;
+
+struct Parser<'s> {
+    input: &'s str,
+    pos: usize,
+    /// Brackets open at `pos`.
+    depth: usize,
+}
+
+/// An expression, and whether it is an operator still waiting for its
+/// brackets. Operators render one pair of their own, so only the first pair
+/// around one is implicit; every further pair is a [`SearchPredicate::Group`].
+type Expression = (SearchPredicate, bool);
+
+impl<'s> Parser<'s> {
+    fn error<T, S: ToString>(&self, position: usize, message: S) -> Result<T, QueryParseError> {
+        Err(QueryParseError {
+            position,
+            message: message.to_string(),
+        })
+    }
+
+    fn rest(&self) -> &'s str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes `keyword` if it comes next as a whole word.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let Some(after) = self.rest().strip_prefix(keyword) else {
+            return false;
+        };
+        if after.is_empty() || after.starts_with(|c: char| c.is_whitespace() || c == '(') {
+            self.pos += keyword.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> Result<Expression, QueryParseError> {
+        let first = self.and()?;
+        let mut operands = vec![];
+        loop {
+            self.skip_whitespace();
+            if !self.keyword("OR") {
+                break;
+            }
+            operands.push(self.and()?.0);
+        }
+
+        if operands.is_empty() {
+            return Ok(first);
+        }
+        operands.insert(0, first.0);
+        Ok((SearchPredicate::Or(operands), true))
+    }
+
+    fn and(&mut self) -> Result<Expression, QueryParseError> {
+        let collapse = |mut operands: Vec<SearchPredicate>| {
+            if operands.len() == 1 {
+                operands.remove(0)
+            } else {
+                SearchPredicate::And(operands)
+            }
+        };
+
+        let mut operands = vec![self.primary()?];
+        let mut is_operator = false;
+        loop {
+            self.skip_whitespace();
+            if self.keyword("ANDNOT") {
+                let rhs = self.primary()?;
+                operands = vec![SearchPredicate::AndNot(
+                    Box::new(collapse(operands)),
+                    Box::new(rhs),
+                )];
+            } else if self.keyword("AND") {
+                operands.push(self.primary()?);
+            } else {
+                break;
+            }
+            is_operator = true;
+        }
+
+        Ok((collapse(operands), is_operator))
+    }
+
+    fn primary(&mut self) -> Result<SearchPredicate, QueryParseError> {
+        self.skip_whitespace();
+        if self.peek() != Some('(') {
+            return self.term();
+        }
+
+        let start = self.pos;
+        if self.depth == MAX_DEPTH {
+            return self.error(
+                start,
+                format!("Brackets nested deeper than {} levels", MAX_DEPTH),
+            );
+        }
+        self.depth += 1;
+        self.pos += 1;
+        self.skip_whitespace();
+        let node = if self.peek() == Some(')') {
+            SearchPredicate::And(vec![])
+        } else {
+            let (inner, is_operator) = self.or()?;
+            self.skip_whitespace();
+            if self.peek() != Some(')') {
+                return self.error(
+                    self.pos,
+                    format!("Expected `)` to close the group opened at {}", start),
+                );
+            }
+            if is_operator {
+                inner
+            } else {
+                SearchPredicate::Group(Box::new(inner))
+            }
+        };
+        self.pos += 1;
+        self.depth -= 1;
+
+        Ok(node)
+    }
+
+    fn term(&mut self) -> Result<SearchPredicate, QueryParseError> {
+        let start = self.pos;
+        let name_len = self
+            .rest()
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(self.rest().len());
+        let name = &self.rest()[..name_len];
+        if name.is_empty() {
+            return match self.peek() {
+                Some(c) => self.error(start, format!("Unexpected `{}`", c)),
+                None => self.error(start, "Unexpected end of query"),
+            };
+        }
+        if !self.rest()[name_len..].starts_with(':') {
+            return self.error(start + name_len, format!("Expected `:` after `{}`", name));
+        }
+        self.pos += name_len + 1;
+
+        if let Some(field) = range_field(name) {
+            return self.range(field);
+        }
+        let Some(field) = search_field(name) else {
+            return self.error(start, format!("Unknown search field `{}`", name));
+        };
+
+        if self.peek() == Some('"') {
+            self.phrase(field)
+        } else {
+            self.word(field)
+        }
+    }
+
+    fn phrase(&mut self, field: SearchField) -> Result<SearchPredicate, QueryParseError> {
+        let start = self.pos;
+        let mut term = String::new();
+        let mut chars = self.rest().char_indices().skip(1);
+        loop {
+            match chars.next() {
+                Some((i, '"')) => {
+                    self.pos += i + 1;
+                    break;
+                }
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, c)) => term.push(c),
+                    None => break,
+                },
+                Some((_, c)) => term.push(c),
+                None => break,
+            }
+        }
+        if self.pos == start {
+            return self.error(start, "Unterminated phrase");
+        }
+        if self.peek().is_some_and(|c| !c.is_whitespace() && c != ')') {
+            return self.error(self.pos, "Expected whitespace after phrase");
+        }
+
+        Ok(SearchPredicate::Term(SearchTerm::phrase(field, term)))
+    }
+
+    fn word(&mut self, field: SearchField) -> Result<SearchPredicate, QueryParseError> {
+        let mut term = String::new();
+        let mut chars = self.rest().char_indices();
+        let mut end = self.rest().len();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some((_, c)) => term.push(c),
+                    None => return self.error(self.pos + i, "Dangling `\\`"),
+                },
+                c if c.is_whitespace() || c == '(' || c == ')' => {
+                    end = i;
+                    break;
+                }
+                c => term.push(c),
+            }
+        }
+        self.pos += end;
+
+        Ok(SearchPredicate::Term(SearchTerm::word(field, term)))
+    }
+
+    fn range(&mut self, field: RangeField) -> Result<SearchPredicate, QueryParseError> {
+        if self.peek() != Some('[') {
+            return self.error(self.pos, "Expected `[` to start a date range");
+        }
+        self.pos += 1;
+        let start = self.range_bound(OPEN_START)?;
+        if !self.rest().starts_with(" TO ") {
+            return self.error(self.pos, "Expected ` TO ` in date range");
+        }
+        self.pos += 4;
+        let end = self.range_bound(OPEN_END)?;
+        if self.peek() != Some(']') {
+            return self.error(self.pos, "Expected `]` to end the date range");
+        }
+        self.pos += 1;
+
+        Ok(SearchPredicate::Range(SearchRange { field, start, end }))
+    }
+
+    fn range_bound(&mut self, open: &str) -> Result<Option<OffsetDateTime>, QueryParseError> {
+        let digits = self
+            .rest()
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest().len());
+        let bound = &self.rest()[..digits];
+        if bound.len() != 12 {
+            return self.error(self.pos, "Expected a date as YYYYMMDDHHMM");
+        }
+        if bound == open {
+            self.pos += digits;
+            return Ok(None);
+        }
+
+        let Ok(timestamp) = PrimitiveDateTime::parse(
+            bound,
+            format_description!("[year][month][day][hour][minute]"),
+        ) else {
+            return self.error(self.pos, format!("Invalid date `{}`", bound));
+        };
+        self.pos += digits;
+
+        Ok(Some(timestamp.assume_utc()))
+    }
+}
+
+impl SearchPredicate {
+    /// Parses a `search_query` string such as
+    /// `ti:RAG AND (au:smith OR abs:"graph neural") ANDNOT cat:cs.CV`.
+    /// `AND` and `ANDNOT` bind tighter than `OR`.
+    ///
+    /// Parsing the `Display` output of a predicate gives a predicate with the
+    /// same `Display` output. An empty query, which is how an empty `And`
+    /// displays, gives an empty `And`.
+    pub fn parse(query: &str) -> Result<Self, QueryParseError> {
+        let mut parser = Parser {
+            input: query,
+            pos: 0,
+            depth: 0,
+        };
+        parser.skip_whitespace();
+        if parser.peek().is_none() {
+            return Ok(SearchPredicate::And(vec![]));
+        }
+        let bracketed = parser.peek() == Some('(');
+        let (node, is_operator) = parser.or()?;
+        parser.skip_whitespace();
+        if let Some(c) = parser.peek() {
+            return parser.error(parser.pos, format!("Unexpected `{}`", c));
+        }
+
+        // `Display` leaves out the brackets of the outermost operator, so
+        // brackets around the whole query belong to a group.
+        if bracketed && !is_operator {
+            return Ok(SearchPredicate::Group(Box::new(node)));
+        }
+
+        Ok(node)
+    }
+}
+
+impl std::str::FromStr for SearchPredicate {
+    type Err = QueryParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::search_query::ISearchQuery;
+
+    /// The tree parsing gives back for `predicate`. It differs only where two
+    /// trees display alike: an `And` or `Or` of one operand displays like a
+    /// group, an empty `Or` like an empty `And`, and a group of a single term
+    /// or range at the top like the term or range, as `Display` drops the
+    /// outermost brackets.
+    fn normalize(predicate: &SearchPredicate) -> SearchPredicate {
+        match normalize_operands(predicate) {
+            SearchPredicate::Group(inner)
+                if matches!(*inner, SearchPredicate::Term(_) | SearchPredicate::Range(_)) =>
+            {
+                *inner
+            }
+            predicate => predicate,
+        }
+    }
+
+    fn normalize_operands(predicate: &SearchPredicate) -> SearchPredicate {
+        match predicate {
+            SearchPredicate::And(operands) | SearchPredicate::Or(operands)
+                if operands.len() == 1 =>
+            {
+                SearchPredicate::group(normalize_operands(&operands[0]))
+            }
+            SearchPredicate::And(operands) => {
+                SearchPredicate::And(operands.iter().map(normalize_operands).collect())
+            }
+            SearchPredicate::Or(operands) if operands.is_empty() => SearchPredicate::And(vec![]),
+            SearchPredicate::Or(operands) => {
+                SearchPredicate::Or(operands.iter().map(normalize_operands).collect())
+            }
+            SearchPredicate::AndNot(lhs, rhs) => {
+                SearchPredicate::and_not(normalize_operands(lhs), normalize_operands(rhs))
+            }
+            SearchPredicate::Group(inner) => SearchPredicate::group(normalize_operands(inner)),
+            SearchPredicate::Term(_) | SearchPredicate::Range(_) => predicate.clone(),
+        }
+    }
+
+    fn assert_round_trip(predicate: &SearchPredicate) {
+        let query = predicate.to_string();
+        let parsed = SearchPredicate::parse(&query).unwrap();
+        assert_eq!(parsed, normalize(predicate), "{}", query);
+        assert_eq!(parsed.to_string(), query);
+    }
+
+    #[test]
+    fn test_parse() {
+        let predicate = SearchPredicate::parse(
+            "ti:RAG AND (au:smith OR abs:\"graph neural\") ANDNOT cat:cs.CV",
+        )
+        .unwrap();
+        assert_eq!(
+            predicate.to_query_string(),
+            "((ti:RAG AND (au:smith OR abs:\"graph neural\")) ANDNOT cat:cs.CV)"
+        );
+
+        let predicate = SearchPredicate::parse("ti:a OR ti:b AND ti:c").unwrap();
+        assert_eq!(predicate.to_string(), "ti:a OR (ti:b AND ti:c)");
+
+        let predicate: SearchPredicate = "  au:del\\ Maestro  ".parse().unwrap();
+        assert_eq!(predicate.to_string(), "au:del\\ Maestro");
+    }
+
+    #[test]
+    fn test_parse_equal_tree() {
+        let predicate = SearchPredicate::and_not(
+            SearchPredicate::and(
+                SearchTerm::new(SearchField::Title, "RAG"),
+                SearchPredicate::or(
+                    SearchTerm::new(SearchField::Author, "smith"),
+                    SearchTerm::new(SearchField::Abstract, "graph neural"),
+                ),
+            ),
+            SearchTerm::new(SearchField::SubjectCategory, "cs.CV"),
+        );
+        assert_eq!(
+            SearchPredicate::parse(&predicate.to_string()).unwrap(),
+            predicate
+        );
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let predicate =
+            SearchPredicate::parse("cat:hep-th AND submittedDate:[202001010000 TO 999912312359]")
+                .unwrap();
+        assert_eq!(
+            predicate.to_string(),
+            "cat:hep-th AND submittedDate:[202001010000 TO 999912312359]"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let term = |field, term| SearchTerm::new(field, term);
+        let start = OffsetDateTime::from_unix_timestamp(0).unwrap();
+        let end = OffsetDateTime::from_unix_timestamp(86_459).unwrap();
+        let first = OffsetDateTime::from_unix_timestamp(662_688_000).unwrap();
+
+        let predicates = vec![
+            SearchPredicate::and(
+                term(SearchField::Title, "RAG"),
+                term(SearchField::Author, "John Doe"),
+            ),
+            SearchPredicate::or(
+                SearchPredicate::and(
+                    term(SearchField::Title, "RAG"),
+                    term(SearchField::Comment, "say \"hi\" (twice)"),
+                ),
+                SearchPredicate::and_not(
+                    SearchTerm::word(SearchField::All, "a:b c"),
+                    SearchRange::new(RangeField::SubmittedDate, start, end),
+                ),
+            ),
+            SearchPredicate::group(term(SearchField::Title, "RAG")),
+            SearchPredicate::group(SearchPredicate::group(term(SearchField::Title, "x"))),
+            SearchPredicate::group(SearchPredicate::and(
+                SearchTerm::phrase(SearchField::Abstract, "graph"),
+                SearchRange::since(RangeField::LastUpdatedDate, start),
+            )),
+            SearchPredicate::or(
+                SearchPredicate::group(term(SearchField::Title, "x")),
+                SearchPredicate::group(term(SearchField::Title, "y")),
+            ),
+            SearchPredicate::and(
+                SearchPredicate::and(
+                    term(SearchField::Doi, "10.1/x"),
+                    term(SearchField::Title, ""),
+                ),
+                term(SearchField::ReportNumber, "CERN-TH-2024"),
+            ),
+            SearchPredicate::and_all([term(SearchField::JournalReference, "PRL")]),
+            SearchPredicate::or_all([SearchPredicate::group(term(SearchField::Title, "x"))]),
+            SearchRange::new(
+                RangeField::SubmittedDate,
+                first,
+                first + time::Duration::seconds(90),
+            )
+            .into(),
+            SearchPredicate::group(SearchPredicate::group(SearchPredicate::and(
+                term(SearchField::Title, "a"),
+                term(SearchField::Title, "b"),
+            ))),
+            SearchPredicate::and(
+                SearchPredicate::group(SearchPredicate::and(
+                    term(SearchField::Title, "a"),
+                    term(SearchField::Title, "b"),
+                )),
+                term(SearchField::Title, "c"),
+            ),
+            SearchPredicate::and_all(Vec::<SearchPredicate>::new()),
+            SearchPredicate::group(SearchPredicate::or_all(Vec::<SearchPredicate>::new())),
+            SearchPredicate::and(
+                SearchPredicate::or_all(Vec::<SearchPredicate>::new()),
+                term(SearchField::Title, "a"),
+            ),
+        ];
+        for predicate in &predicates {
+            assert_round_trip(predicate);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |query: &str| SearchPredicate::parse(query).err().unwrap();
+
+        assert_eq!(error("ti:RAG AND (au:smith").position, 20);
+        assert_eq!(error("ti:RAG AND xx:smith").position, 11);
+        assert_eq!(
+            error("ti:RAG AND xx:smith").message,
+            "Unknown search field `xx`"
+        );
+        assert_eq!(error("ti:RAG AND").position, 10);
+        assert_eq!(error("ti:\"graph neural").position, 3);
+        assert_eq!(error("ti:RAG smith").position, 7);
+        assert_eq!(error("RAG").position, 3);
+        assert_eq!(error("submittedDate:[2024 TO 202401010000]").position, 15);
+        assert_eq!(
+            error("ti:RAG )").to_string(),
+            "Unexpected `)` at position 7"
+        );
+
+        let nested = format!("{}ti:RAG{}", "(".repeat(10_000), ")".repeat(10_000));
+        assert_eq!(error(&nested).position, MAX_DEPTH);
+        let nested = format!("{}ti:RAG{}", "(".repeat(MAX_DEPTH), ")".repeat(MAX_DEPTH));
+        assert!(SearchPredicate::parse(&nested).is_ok());
+    }
+}
@@ -0,0 +1,441 @@
+use std::io::BufRead;
+
+use anyhow::anyhow;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use tokio::io::AsyncBufRead;
+
+use crate::models::{ArxivResult, Entry, EntryAuthor, EntryCategory, EntryLink, FieldError};
+use crate::parse::{diagnostics_from, ParseDiagnostic, ParseMode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextField {
+    Id,
+    Title,
+    Summary,
+    Updated,
+    Published,
+    AuthorName,
+    Affiliation,
+    Doi,
+    Comment,
+    JournalRef,
+}
+
+fn attribute(e: &BytesStart<'_>, name: &str) -> anyhow::Result<Option<String>> {
+    match e.try_get_attribute(name)? {
+        Some(attr) => Ok(Some(attr.unescape_value()?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+/// Builds entries directly from reader events, without materializing the
+/// response body or an intermediate serde tree.
+#[derive(Debug, Default)]
+struct EntryBuilder {
+    entry: Option<Entry>,
+    errors: Vec<FieldError>,
+    field: Option<TextField>,
+    text: String,
+    author: Option<Option<String>>,
+    affiliations: Vec<String>,
+    n_entries: usize,
+}
+
+type Built = Result<ArxivResult, Vec<ParseDiagnostic>>;
+
+impl EntryBuilder {
+    fn start(&mut self, e: &BytesStart<'_>) -> anyhow::Result<()> {
+        let local_name = e.local_name();
+        let name = local_name.as_ref();
+
+        if name == b"entry" {
+            self.entry = Some(Entry::default());
+            self.errors.clear();
+            return Ok(());
+        }
+        if self.entry.is_none() {
+            return Ok(());
+        }
+
+        self.field = match name {
+            b"id" => Some(TextField::Id),
+            b"title" => Some(TextField::Title),
+            b"summary" => Some(TextField::Summary),
+            b"updated" => Some(TextField::Updated),
+            b"published" => Some(TextField::Published),
+            b"name" if self.author.is_some() => Some(TextField::AuthorName),
+            b"affiliation" if self.author.is_some() => Some(TextField::Affiliation),
+            b"doi" => Some(TextField::Doi),
+            b"comment" => Some(TextField::Comment),
+            b"journal_ref" => Some(TextField::JournalRef),
+            _ => None,
+        };
+        self.text.clear();
+
+        if name == b"author" {
+            self.author = Some(None);
+            self.affiliations.clear();
+        }
+        self.attributes(e)
+    }
+
+    fn attributes(&mut self, e: &BytesStart<'_>) -> anyhow::Result<()> {
+        let Some(entry) = self.entry.as_mut() else {
+            return Ok(());
+        };
+
+        match e.local_name().as_ref() {
+            b"link" => match attribute(e, "href")? {
+                Some(href) => entry.links.push(EntryLink {
+                    title: attribute(e, "title")?,
+                    rel: attribute(e, "rel")?.unwrap_or_default(),
+                    href,
+                    content_type: attribute(e, "type")?,
+                }),
+                None => self.errors.push(FieldError {
+                    field: "link",
+                    reason: "missing href".to_string(),
+                }),
+            },
+            name @ (b"primary_category" | b"category") => {
+                let field = if name == b"category" {
+                    "category"
+                } else {
+                    "primary_category"
+                };
+                match attribute(e, "term")? {
+                    Some(term) => {
+                        let category = EntryCategory {
+                            term,
+                            scheme: attribute(e, "scheme")?,
+                        };
+                        if field == "category" {
+                            entry.categories.push(category);
+                        } else {
+                            entry.primary_category = Some(category);
+                        }
+                    }
+                    None => self.errors.push(FieldError {
+                        field,
+                        reason: "missing term".to_string(),
+                    }),
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.field.is_some() {
+            self.text.push_str(text);
+        }
+    }
+
+    fn end(&mut self, local_name: &[u8]) -> Option<Built> {
+        let entry = self.entry.as_mut()?;
+
+        if let Some(field) = self.field.take() {
+            let text = std::mem::take(&mut self.text).trim().to_string();
+            let slot = match field {
+                TextField::Id => &mut entry.id,
+                TextField::Title => &mut entry.title,
+                TextField::Summary => &mut entry.summary,
+                TextField::Updated => &mut entry.updated,
+                TextField::Published => &mut entry.published,
+                TextField::Doi => &mut entry.doi,
+                TextField::Comment => &mut entry.comment,
+                TextField::JournalRef => &mut entry.journal_ref,
+                TextField::AuthorName => self.author.as_mut()?,
+                TextField::Affiliation => {
+                    self.affiliations.push(text);
+                    return None;
+                }
+            };
+            *slot = Some(text);
+            return None;
+        }
+
+        match local_name {
+            b"author" => {
+                match self.author.take().flatten() {
+                    Some(name) => entry.authors.push(EntryAuthor {
+                        name,
+                        affiliations: std::mem::take(&mut self.affiliations),
+                    }),
+                    None => self.errors.push(FieldError {
+                        field: "author",
+                        reason: "missing name".to_string(),
+                    }),
+                }
+                None
+            }
+            b"entry" => {
+                let entry = self.entry.take()?;
+                let index = self.n_entries;
+                self.n_entries += 1;
+
+                let entry_id = entry.id.clone();
+                let mut errors = std::mem::take(&mut self.errors);
+                let result = match ArxivResult::try_from_entry(entry) {
+                    Ok(result) if errors.is_empty() => Ok(result),
+                    Ok(_) => Err(diagnostics_from(index, entry_id, errors)),
+                    Err(field_errors) => {
+                        errors.extend(field_errors);
+                        Err(diagnostics_from(index, entry_id, errors))
+                    }
+                };
+                Some(result)
+            }
+            _ => None,
+        }
+    }
+
+    fn handle(&mut self, event: Event<'_>) -> anyhow::Result<Option<Built>> {
+        match event {
+            Event::Start(e) => self.start(&e)?,
+            Event::Empty(e) => {
+                if e.local_name().as_ref() == b"entry" {
+                    self.start(&e)?;
+                    return Ok(self.end(b"entry"));
+                }
+                self.attributes(&e)?;
+            }
+            Event::Text(e) => self.text(&e.unescape()?),
+            Event::CData(e) => self.text(std::str::from_utf8(&e)?),
+            Event::End(e) => return Ok(self.end(e.local_name().as_ref())),
+            _ => {}
+        }
+
+        Ok(None)
+    }
+}
+
+/// State shared by the blocking and async readers.
+#[derive(Debug, Default)]
+struct FeedState {
+    builder: EntryBuilder,
+    mode: ParseMode,
+    diagnostics: Vec<ParseDiagnostic>,
+    is_feed: bool,
+    done: bool,
+}
+
+impl FeedState {
+    /// `None` means "keep reading"; `Some(item)` is what `next` returns.
+    fn on_event(
+        &mut self,
+        event: quick_xml::Result<Event<'_>>,
+    ) -> Option<Option<anyhow::Result<ArxivResult>>> {
+        let event = match event {
+            Ok(Event::Eof) => {
+                self.done = true;
+                if !self.is_feed {
+                    return Some(Some(Err(anyhow!("Response is not an Atom feed"))));
+                }
+                return Some(None);
+            }
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"feed" => {
+                self.is_feed = true;
+                return None;
+            }
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"feed" => {
+                self.is_feed = true;
+                return None;
+            }
+            Ok(event) => event,
+            Err(e) => {
+                self.done = true;
+                return Some(Some(Err(e.into())));
+            }
+        };
+
+        match self.builder.handle(event) {
+            Ok(None) => None,
+            Ok(Some(Ok(result))) => Some(Some(Ok(result))),
+            Ok(Some(Err(diagnostics))) => match self.mode {
+                ParseMode::Lenient => {
+                    self.diagnostics.extend(diagnostics);
+                    None
+                }
+                ParseMode::Strict => {
+                    self.done = true;
+                    Some(Some(Err(anyhow!(
+                        "Failed to parse feed: {}",
+                        diagnostics[0]
+                    ))))
+                }
+            },
+            Err(e) => {
+                self.done = true;
+                Some(Some(Err(e)))
+            }
+        }
+    }
+}
+
+fn configured<R>(mut reader: Reader<R>) -> Reader<R> {
+    reader.config_mut().trim_text(true);
+    reader
+}
+
+/// Yields results one entry at a time from any [`BufRead`], so large feeds
+/// never have to be held in memory as a whole.
+///
+/// In [`ParseMode::Lenient`] bad entries are skipped and collected in
+/// [`FeedReader::diagnostics`]; in [`ParseMode::Strict`] the first one ends
+/// the iteration with an error.
+pub struct FeedReader<R> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    state: FeedState,
+}
+
+impl<R: BufRead> FeedReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: configured(Reader::from_reader(reader)),
+            buf: Vec::new(),
+            state: FeedState::default(),
+        }
+    }
+
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.state.mode = mode;
+        self
+    }
+
+    pub fn diagnostics(&self) -> &[ParseDiagnostic] {
+        &self.state.diagnostics
+    }
+
+    pub fn into_diagnostics(self) -> Vec<ParseDiagnostic> {
+        self.state.diagnostics
+    }
+}
+
+impl<R: BufRead> Iterator for FeedReader<R> {
+    type Item = anyhow::Result<ArxivResult>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.state.done {
+            self.buf.clear();
+            let event = self.reader.read_event_into(&mut self.buf);
+            if let Some(item) = self.state.on_event(event) {
+                return item;
+            }
+        }
+
+        None
+    }
+}
+
+/// The async counterpart of [`FeedReader`], returned by
+/// [`crate::ArxivClient::search_stream`].
+pub struct FeedStream<R> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    state: FeedState,
+}
+
+impl<R: AsyncBufRead + Unpin> FeedStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: configured(Reader::from_reader(reader)),
+            buf: Vec::new(),
+            state: FeedState::default(),
+        }
+    }
+
+    pub fn with_mode(mut self, mode: ParseMode) -> Self {
+        self.state.mode = mode;
+        self
+    }
+
+    pub async fn next_result(&mut self) -> Option<anyhow::Result<ArxivResult>> {
+        while !self.state.done {
+            self.buf.clear();
+            let event = self.reader.read_event_into_async(&mut self.buf).await;
+            if let Some(item) = self.state.on_event(event) {
+                return item;
+            }
+        }
+
+        None
+    }
+
+    pub fn diagnostics(&self) -> &[ParseDiagnostic] {
+        &self.state.diagnostics
+    }
+
+    pub fn into_diagnostics(self) -> Vec<ParseDiagnostic> {
+        self.state.diagnostics
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::FEED;
+    use crate::parse::parse_feed;
+
+    #[test]
+    fn test_matches_serde_path() {
+        let streamed = FeedReader::new(FEED.as_bytes())
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+        let parsed = parse_feed(FEED).unwrap();
+
+        assert_eq!(format!("{:?}", streamed), format!("{:?}", parsed.results));
+    }
+
+    #[test]
+    fn test_lenient_and_strict() {
+        let feed = FEED
+            .replacen(
+                "<updated>2024-02-23T18:35:15Z</updated>",
+                "<updated>yesterday</updated>",
+                1,
+            )
+            .replacen("<name>Pengfei He</name>", "", 1);
+
+        let mut reader = FeedReader::new(feed.as_bytes());
+        let ids = reader
+            .by_ref()
+            .map(|result| result.unwrap().id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 2);
+
+        let fields = reader
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.field.as_deref().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(fields, vec!["author", "updated"]);
+
+        let mut reader = FeedReader::new(feed.as_bytes()).with_mode(ParseMode::Strict);
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_feed_stream() {
+        let mut stream = FeedStream::new(FEED.as_bytes());
+        let mut ids = vec![];
+        while let Some(result) = stream.next_result().await {
+            ids.push(result.unwrap().id);
+        }
+
+        assert_eq!(
+            ids,
+            vec![
+                "http://arxiv.org/abs/2402.16893v1",
+                "http://arxiv.org/abs/hep-th/9901001v2",
+                "http://arxiv.org/abs/2101.00027v3",
+            ]
+        );
+        assert!(stream.diagnostics().is_empty());
+    }
+}
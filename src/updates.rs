@@ -0,0 +1,309 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::export::plain;
+use crate::id::ArxivId;
+use crate::models::{timestamp_format, ArxivResult};
+use crate::parse::{ParseDiagnostic, SearchResults};
+use crate::ArxivClient;
+
+/// Papers per id_list request when checking for updates.
+const BATCH_SIZE: usize = 100;
+
+/// The last known state of a paper on a reading list.
+///
+/// Title and authors are optional; when stored, changes to them are reported
+/// as well.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrackedPaper {
+    /// The base identifier; any version suffix is ignored.
+    pub id: ArxivId,
+    pub version: u32,
+    #[serde(with = "timestamp_format")]
+    pub updated: OffsetDateTime,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub authors: Option<Vec<String>>,
+}
+
+impl TrackedPaper {
+    pub fn new(id: ArxivId, version: u32, updated: OffsetDateTime) -> Self {
+        Self {
+            id: id.without_version(),
+            version,
+            updated,
+            title: None,
+            authors: None,
+        }
+    }
+
+    pub fn with_title<S: ToString>(mut self, title: S) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn with_authors(mut self, authors: Vec<String>) -> Self {
+        self.authors = Some(authors);
+        self
+    }
+
+    /// The full state of `result`, or `None` if its id has no version.
+    pub fn from_result(result: &ArxivResult) -> Option<Self> {
+        let id = result.arxiv_id()?;
+        let version = id.version()?;
+
+        Some(
+            Self::new(id, version, result.updated)
+                .with_title(&result.title)
+                .with_authors(result.authors.clone()),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaperChange {
+    NewVersion { from: u32, to: u32 },
+    Withdrawn,
+    TitleChanged { from: String, to: String },
+    AuthorsChanged { from: Vec<String>, to: Vec<String> },
+}
+
+/// A tracked paper that changed, with its current metadata.
+#[derive(Debug, Clone)]
+pub struct PaperUpdate {
+    pub tracked: TrackedPaper,
+    pub changes: Vec<PaperChange>,
+    pub current: ArxivResult,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UpdateReport {
+    pub updates: Vec<PaperUpdate>,
+    /// Tracked papers arXiv returned nothing for.
+    pub missing: Vec<ArxivId>,
+    /// Tracked papers arXiv returned an entry for that could not be parsed.
+    pub unparsed: Vec<ArxivId>,
+    /// Why entries were skipped. A paper whose entry has no readable id is
+    /// listed under `missing`, the diagnostic only shows up here.
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+fn changes(tracked: &TrackedPaper, current: &ArxivResult) -> Vec<PaperChange> {
+    let mut changes = vec![];
+
+    let version = current.arxiv_id().and_then(|id| id.version());
+    let is_new = match version {
+        Some(version) => version > tracked.version,
+        None => current.updated > tracked.updated,
+    };
+    if is_new {
+        changes.push(PaperChange::NewVersion {
+            from: tracked.version,
+            to: version.unwrap_or(tracked.version),
+        });
+        if current.is_withdrawn() {
+            changes.push(PaperChange::Withdrawn);
+        }
+    }
+
+    // Titles come back with the line breaks of the submission.
+    if let Some(title) = &tracked.title {
+        if plain(title) != plain(&current.title) {
+            changes.push(PaperChange::TitleChanged {
+                from: title.clone(),
+                to: current.title.clone(),
+            });
+        }
+    }
+    if let Some(authors) = &tracked.authors {
+        if *authors != current.authors {
+            changes.push(PaperChange::AuthorsChanged {
+                from: authors.clone(),
+                to: current.authors.clone(),
+            });
+        }
+    }
+
+    changes
+}
+
+fn compare(tracked: &[TrackedPaper], results: SearchResults) -> UpdateReport {
+    let mut current = results
+        .results
+        .into_iter()
+        .filter_map(|result| Some((result.arxiv_id()?.base().to_string(), result)))
+        .collect::<HashMap<_, _>>();
+    let unparsed = results
+        .diagnostics
+        .iter()
+        .filter_map(|diagnostic| ArxivId::parse(diagnostic.entry_id.as_deref()?).ok())
+        .map(|id| id.base().to_string())
+        .collect::<HashSet<_>>();
+    let mut report = UpdateReport {
+        diagnostics: results.diagnostics,
+        ..Default::default()
+    };
+
+    for paper in tracked {
+        let Some(result) = current.remove(paper.id.base()) else {
+            if unparsed.contains(paper.id.base()) {
+                report.unparsed.push(paper.id.clone());
+            } else {
+                report.missing.push(paper.id.clone());
+            }
+            continue;
+        };
+        let changes = changes(paper, &result);
+        if !changes.is_empty() {
+            report.updates.push(PaperUpdate {
+                tracked: paper.clone(),
+                changes,
+                current: result,
+            });
+        }
+    }
+
+    report
+}
+
+impl ArxivClient {
+    /// Looks up the latest version of every tracked paper, a batch of
+    /// papers per request, and reports those that changed.
+    pub async fn check_updates(&self, tracked: &[TrackedPaper]) -> anyhow::Result<UpdateReport> {
+        let mut report = UpdateReport::default();
+
+        for (i, batch) in tracked.chunks(BATCH_SIZE).enumerate() {
+            if i > 0 {
+                tokio::time::sleep(self.interval).await;
+            }
+
+            let ids = batch
+                .iter()
+                .map(|paper| paper.id.base().to_string())
+                .collect();
+            let batch_report = compare(batch, self.fetch_ids(ids).await?);
+            report.updates.extend(batch_report.updates);
+            report.missing.extend(batch_report.missing);
+            report.unparsed.extend(batch_report.unparsed);
+            report.diagnostics.extend(batch_report.diagnostics);
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    fn parsed(results: Vec<ArxivResult>) -> SearchResults {
+        SearchResults {
+            results,
+            diagnostics: vec![],
+        }
+    }
+
+    fn tracked() -> Vec<TrackedPaper> {
+        fixture()
+            .iter()
+            .map(|result| TrackedPaper::from_result(result).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_unchanged() {
+        let report = compare(&tracked(), parsed(fixture()));
+        assert!(report.updates.is_empty());
+        assert!(report.missing.is_empty());
+    }
+
+    #[test]
+    fn test_changes() {
+        let mut tracked = tracked();
+        tracked[0].title = Some("The Good and The Bad".to_string());
+        tracked[2].version = 2;
+
+        let mut results = fixture();
+        results.remove(1);
+        results[0].id = "http://arxiv.org/abs/2402.16893v2".to_string();
+        results[0].authors.pop();
+
+        let report = compare(&tracked, parsed(results));
+        assert_eq!(
+            report.missing,
+            vec![ArxivId::parse("hep-th/9901001").unwrap()]
+        );
+        assert_eq!(report.updates.len(), 2);
+
+        let update = &report.updates[0];
+        assert_eq!(update.tracked.id.base(), "2402.16893");
+        assert_eq!(
+            update.changes[0],
+            PaperChange::NewVersion { from: 1, to: 2 }
+        );
+        assert!(matches!(
+            update.changes[1],
+            PaperChange::TitleChanged { .. }
+        ));
+        assert!(matches!(
+            update.changes[2],
+            PaperChange::AuthorsChanged { .. }
+        ));
+
+        assert_eq!(
+            report.updates[1].changes,
+            vec![
+                PaperChange::NewVersion { from: 2, to: 3 },
+                PaperChange::Withdrawn
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unparsed() {
+        let mut results = fixture();
+        results.remove(1);
+        let diagnostic = ParseDiagnostic {
+            entry_index: 1,
+            entry_id: Some("http://arxiv.org/abs/hep-th/9901001v2".to_string()),
+            field: Some("published".to_string()),
+            reason: "invalid timestamp".to_string(),
+        };
+        let report = compare(
+            &tracked(),
+            SearchResults {
+                results,
+                diagnostics: vec![diagnostic.clone()],
+            },
+        );
+
+        assert!(report.missing.is_empty());
+        assert_eq!(
+            report.unparsed,
+            vec![ArxivId::parse("hep-th/9901001").unwrap()]
+        );
+        assert_eq!(report.diagnostics, vec![diagnostic]);
+    }
+
+    #[test]
+    fn test_title_whitespace() {
+        let mut tracked = tracked();
+        tracked[0].title = Some(plain(tracked[0].title.as_ref().unwrap()));
+        assert!(compare(&tracked, parsed(fixture())).updates.is_empty());
+    }
+
+    #[test]
+    fn test_serde() {
+        let paper = &tracked()[1];
+        let json = serde_json::to_value(paper).unwrap();
+        assert_eq!(json["id"], "hep-th/9901001");
+        assert_eq!(json["version"], 2);
+
+        let parsed: TrackedPaper = serde_json::from_value(json).unwrap();
+        assert_eq!(&parsed, paper);
+    }
+}
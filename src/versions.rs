@@ -0,0 +1,137 @@
+use anyhow::{anyhow, bail};
+use time::OffsetDateTime;
+
+use crate::id::ArxivId;
+use crate::models::ArxivResult;
+use crate::parse::SearchResults;
+use crate::query::ArxivQuery;
+use crate::{ArxivClient, BASE_URL};
+
+/// One revision of a paper, with the metadata as of that revision.
+#[derive(Debug, Clone)]
+pub struct PaperVersion {
+    pub version: u32,
+    /// When this version was submitted.
+    pub date: OffsetDateTime,
+    pub result: ArxivResult,
+}
+
+/// Orders the results of an id_list query for `v1..=latest` and checks that
+/// none is missing.
+fn collect_versions(
+    id: &ArxivId,
+    latest: u32,
+    results: Vec<ArxivResult>,
+) -> anyhow::Result<Vec<PaperVersion>> {
+    let mut versions = results
+        .into_iter()
+        .filter_map(|result| {
+            let version = result
+                .arxiv_id()
+                .filter(|result_id| result_id.base() == id.base())?
+                .version()?;
+            Some(PaperVersion {
+                version,
+                date: result.updated,
+                result,
+            })
+        })
+        .collect::<Vec<_>>();
+    versions.sort_by_key(|version| version.version);
+    versions.dedup_by_key(|version| version.version);
+
+    let found = versions
+        .iter()
+        .map(|version| version.version)
+        .collect::<Vec<_>>();
+    if found != (1..=latest).collect::<Vec<_>>() {
+        bail!(
+            "Incomplete version history for {}: expected v1 to v{}, got {:?}",
+            id.base(),
+            latest,
+            found
+        );
+    }
+
+    Ok(versions)
+}
+
+impl ArxivClient {
+    pub(crate) async fn fetch_ids(&self, ids: Vec<String>) -> anyhow::Result<SearchResults> {
+        let query: ArxivQuery<String> = ArxivQuery::default()
+            .with_max_results(ids.len())
+            .with_id_list(ids);
+
+        self.fetch(&query.to_url(BASE_URL)?).await
+    }
+
+    async fn fetch_id(&self, id: &ArxivId) -> anyhow::Result<ArxivResult> {
+        self.fetch_ids(vec![id.to_string()])
+            .await?
+            .into_results()
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No paper found for id {}", id))
+    }
+
+    /// Fetches the metadata of `version` of a paper. Any version in `id` is
+    /// ignored.
+    pub async fn fetch_version(&self, id: &ArxivId, version: u32) -> anyhow::Result<ArxivResult> {
+        self.fetch_id(&id.with_version(version)).await
+    }
+
+    /// Lists every version of a paper, oldest first. This takes two requests:
+    /// one for the latest version and one for the metadata of all of them.
+    pub async fn versions(&self, id: &ArxivId) -> anyhow::Result<Vec<PaperVersion>> {
+        let latest = self.fetch_id(&id.without_version()).await?;
+        let latest = latest
+            .arxiv_id()
+            .and_then(|latest| latest.version())
+            .ok_or_else(|| anyhow!("Unexpected id in response: {}", latest.id))?;
+
+        tokio::time::sleep(self.interval).await;
+        let ids = (1..=latest)
+            .map(|version| id.with_version(version).to_string())
+            .collect();
+        collect_versions(id, latest, self.fetch_ids(ids).await?.into_results())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    fn revision(version: u32, day: u8) -> ArxivResult {
+        let mut result = fixture()[0].clone();
+        result.id = format!("http://arxiv.org/abs/2402.16893v{}", version);
+        result.updated = result.updated.replace_day(day).unwrap();
+        result
+    }
+
+    #[test]
+    fn test_collect_versions() {
+        let id = ArxivId::parse("2402.16893").unwrap();
+        let results = vec![revision(2, 25), revision(1, 23), revision(3, 28)];
+
+        let versions = collect_versions(&id, 3, results).unwrap();
+        assert_eq!(
+            versions
+                .iter()
+                .map(|version| (version.version, version.date.day()))
+                .collect::<Vec<_>>(),
+            vec![(1, 23), (2, 25), (3, 28)]
+        );
+        assert_eq!(versions[1].result.id, "http://arxiv.org/abs/2402.16893v2");
+    }
+
+    #[test]
+    fn test_missing_version() {
+        let id = ArxivId::parse("2402.16893v3").unwrap();
+        let err = collect_versions(&id, 3, vec![revision(1, 23), revision(3, 28)]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Incomplete version history for 2402.16893: expected v1 to v3, got [1, 3]"
+        );
+    }
+}
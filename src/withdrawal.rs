@@ -0,0 +1,84 @@
+use crate::models::ArxivResult;
+
+const WITHDRAWAL_PHRASES: &[&str] = &[
+    "has been withdrawn",
+    "have been withdrawn",
+    "is withdrawn",
+    "was withdrawn",
+    "paper withdrawn",
+    "article withdrawn",
+    "submission withdrawn",
+    "withdrawn by",
+    "withdrawn due",
+    "withdrawn because",
+    "withdrawn for",
+    "withdraw this paper",
+    "withdraw this submission",
+    "withdraw this article",
+];
+
+/// Withdrawal notices that replace the abstract are short; longer abstracts
+/// merely mentioning a withdrawal are ordinary papers.
+const MAX_WITHDRAWN_SUMMARY_WORDS: usize = 60;
+
+pub(crate) fn mentions_withdrawal(text: &str) -> bool {
+    let text = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+
+    text.starts_with("withdrawn")
+        || WITHDRAWAL_PHRASES
+            .iter()
+            .any(|phrase| text.contains(phrase))
+}
+
+impl ArxivResult {
+    /// Heuristically detects withdrawn papers, which arXiv still returns with
+    /// a withdrawal notice in the comment and usually a tiny abstract.
+    pub fn is_withdrawn(&self) -> bool {
+        let comment_withdrawn = self.comment.as_deref().is_some_and(mentions_withdrawal);
+        let summary_withdrawn = self.summary.split_whitespace().count()
+            <= MAX_WITHDRAWN_SUMMARY_WORDS
+            && mentions_withdrawal(&self.summary);
+
+        comment_withdrawn || summary_withdrawn
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::models::test::fixture;
+
+    #[test]
+    fn test_mentions_withdrawal() {
+        assert!(mentions_withdrawal(
+            "This paper has been withdrawn by the author"
+        ));
+        assert!(mentions_withdrawal("Withdrawn: duplicate submission"));
+        assert!(mentions_withdrawal(
+            "arXiv admin note: this submission has been\n  withdrawn"
+        ));
+        assert!(!mentions_withdrawal("12 pages, 5 figures"));
+    }
+
+    #[test]
+    fn test_is_withdrawn() {
+        let mut results = fixture();
+        assert!(!results[0].is_withdrawn());
+        assert!(results[2].is_withdrawn());
+
+        results[1].comment = None;
+        results[1].summary =
+            "  This paper has been withdrawn due to a crucial error in Eq. 3.\n".to_string();
+        assert!(results[1].is_withdrawn());
+
+        results[1].summary = format!(
+            "{} Earlier work on this topic was withdrawn by its authors.",
+            "word ".repeat(100)
+        );
+        assert!(!results[1].is_withdrawn());
+    }
+}